@@ -0,0 +1,214 @@
+//! Scripted server that plays one match against any websocket client and checks that
+//! the client follows the protocol. Start it instead of the server and run your bot:
+//!
+//! ```sh
+//! cargo run -- 127.0.0.1:8080
+//! ```
+
+use std::{
+    env,
+    fmt::Display,
+    io::ErrorKind,
+    net::{TcpListener, TcpStream},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use ez_colorize::ColorizeDisplay;
+use protocol::message::{MatchEnd, Response, ACCEPT_CONNECTION};
+use tungstenite::{accept, Message, WebSocket};
+
+const GAME_INFO: &str = include_str!("../../json_example/game_info.json");
+const MATCH_END: &str = include_str!("../../json_example/match_end.json");
+
+const ACTIONS_COUNT: usize = 2;
+const TURN_DEADLINE: Duration = Duration::from_secs(2);
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(60);
+const RECONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Check {
+    name: &'static str,
+    result: Result<(), String>,
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.result {
+            Ok(()) => write!(f, "[{}] {}", "PASS".green(), self.name),
+            Err(err) => write!(f, "[{}] {}: {}", "FAIL".red(), self.name, err),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Report {
+    checks: Vec<Check>,
+}
+
+impl Report {
+    fn check(&mut self, name: &'static str, result: Result<(), String>) -> bool {
+        let ok = result.is_ok();
+        self.checks.push(Check { name, result });
+        ok
+    }
+
+    fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.result.is_ok())
+    }
+}
+
+fn main() -> ExitCode {
+    let addr = env::args()
+        .nth(1)
+        .unwrap_or_else(|| String::from("127.0.0.1:8080"));
+    let listener = match TcpListener::bind(&addr) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("Can't listen on {}: {}", addr, err);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("Waiting for the client on ws://{}", addr);
+    let mut report = Report::default();
+    play(&listener, &mut report);
+    for check in report.checks.iter() {
+        println!("{}", check);
+    }
+    if report.passed() {
+        println!("{}", "The client follows the protocol".green());
+        ExitCode::SUCCESS
+    } else {
+        println!("{}", "The client breaks the protocol".red());
+        ExitCode::FAILURE
+    }
+}
+
+fn play(listener: &TcpListener, report: &mut Report) {
+    let mut ws = match connect(listener, CONNECTION_TIMEOUT) {
+        Ok(ws) => ws,
+        Err(err) => {
+            report.check("Handshake", Err(err));
+            return;
+        }
+    };
+    let handshake = ws
+        .write_message(Message::Text(String::from(ACCEPT_CONNECTION)))
+        .map_err(|err| err.to_string());
+    if !report.check("Handshake", handshake) {
+        return;
+    }
+
+    let turn = send(&mut ws, GAME_INFO).and_then(|()| read_actions(&mut ws));
+    let actions = match turn {
+        Ok(actions) => actions,
+        Err(err) => {
+            report.check("Answers the game state with actions in time", Err(err));
+            return;
+        }
+    };
+    report.check("Answers the game state with actions in time", Ok(()));
+    report.check(
+        "Sends the right number of actions",
+        match actions.actions.len() {
+            ACTIONS_COUNT => Ok(()),
+            count => Err(format!("{} actions instead of {}", count, ACTIONS_COUNT)),
+        },
+    );
+
+    let unexpected = send(&mut ws, "Unexpected message")
+        .and_then(|()| {
+            ws.write_message(Message::Ping(Vec::new()))
+                .map_err(|err| err.to_string())
+        })
+        .and_then(|()| send(&mut ws, GAME_INFO))
+        .and_then(|()| read_actions(&mut ws));
+    report.check("Copes with unexpected messages", unexpected.map(|_| ()));
+
+    let unknown_fields = with_unknown_field(GAME_INFO)
+        .and_then(|info| send(&mut ws, &info))
+        .and_then(|()| read_actions(&mut ws));
+    report.check(
+        "Ignores unknown fields of the game state",
+        unknown_fields.map(|_| ()),
+    );
+
+    let end = send(&mut ws, MATCH_END).and_then(|()| after_match_end(&mut ws));
+    report.check("Stops sending actions after the match end", end);
+
+    report.check(
+        "Reconnects for the next match",
+        connect(listener, RECONNECTION_TIMEOUT).map(|_| ()),
+    );
+}
+
+fn connect(listener: &TcpListener, timeout: Duration) -> Result<WebSocket<TcpStream>, String> {
+    listener
+        .set_nonblocking(true)
+        .map_err(|err| err.to_string())?;
+    let start = Instant::now();
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                stream
+                    .set_nonblocking(false)
+                    .map_err(|err| err.to_string())?;
+                stream
+                    .set_read_timeout(Some(TURN_DEADLINE))
+                    .map_err(|err| err.to_string())?;
+                return accept(stream).map_err(|err| format!("WebSocket handshake: {}", err));
+            }
+            Err(ref err) if err.kind() == ErrorKind::WouldBlock => {
+                if start.elapsed() > timeout {
+                    return Err(format!("no connection in {:?}", timeout));
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+}
+
+fn send(ws: &mut WebSocket<TcpStream>, text: &str) -> Result<(), String> {
+    ws.write_message(Message::Text(String::from(text)))
+        .map_err(|err| err.to_string())
+}
+
+/// Reads the answer to the game state, it has to come before the turn deadline.
+fn read_actions(ws: &mut WebSocket<TcpStream>) -> Result<Response, String> {
+    let start = Instant::now();
+    loop {
+        match ws.read_message() {
+            Ok(Message::Text(text)) => {
+                return serde_json::from_str(&text)
+                    .map_err(|err| format!("wrong actions JSON {:?}: {}", text, err));
+            }
+            Ok(Message::Close(_)) => return Err(String::from("connection closed")),
+            Ok(_) => continue,
+            Err(tungstenite::Error::Io(err))
+                if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+            {
+                return Err(format!("no answer in {:?}", start.elapsed()));
+            }
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+}
+
+/// After the match end the client may close the connection or stay silent.
+fn after_match_end(ws: &mut WebSocket<TcpStream>) -> Result<(), String> {
+    serde_json::from_str::<MatchEnd>(MATCH_END).map_err(|err| err.to_string())?;
+    loop {
+        match ws.read_message() {
+            Ok(Message::Text(text)) => return Err(format!("got {:?}", text)),
+            Ok(Message::Close(_)) => return Ok(()),
+            Ok(_) => continue,
+            Err(_) => return Ok(()),
+        }
+    }
+}
+
+fn with_unknown_field(json: &str) -> Result<String, String> {
+    let mut info: serde_json::Value = serde_json::from_str(json).map_err(|err| err.to_string())?;
+    info["field_from_the_future"] = serde_json::json!({ "value": 1 });
+    Ok(info.to_string())
+}
@@ -0,0 +1,33 @@
+pub use protocol::ability::{
+    Cooldowns, Mine, DASH_COOLDOWN, DASH_RANGE, MINE_COOLDOWN, MINE_DAMAGE, REVEAL_COOLDOWN,
+    REVEAL_TURNS, SHIELD_AMOUNT, SHIELD_COOLDOWN,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ability {
+    Dash,
+    Shield,
+    Mine,
+    Reveal,
+}
+
+impl Ability {
+    pub fn cooldown(self) -> u8 {
+        match self {
+            Ability::Dash => DASH_COOLDOWN,
+            Ability::Shield => SHIELD_COOLDOWN,
+            Ability::Mine => MINE_COOLDOWN,
+            Ability::Reveal => REVEAL_COOLDOWN,
+        }
+    }
+
+    /// Cooldown timer of the ability.
+    pub fn timer(self, cooldowns: &mut Cooldowns) -> &mut u8 {
+        match self {
+            Ability::Dash => &mut cooldowns.dash,
+            Ability::Shield => &mut cooldowns.shield,
+            Ability::Mine => &mut cooldowns.mine,
+            Ability::Reveal => &mut cooldowns.reveal,
+        }
+    }
+}
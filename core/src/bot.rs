@@ -0,0 +1,215 @@
+use protocol::geometry::occupied;
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    character::CharacterInfo, direction::Direction, game::GameInfo, map::CanMove, player::Action,
+    pos::Pos,
+};
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Top,
+    Direction::Bottom,
+    Direction::Right,
+    Direction::Left,
+];
+
+/// Server side program that plays for a bot.
+pub trait Controller {
+    /// Actions of the hero `info.me`.
+    fn actions(&mut self, info: &GameInfo, actions_count: usize) -> Vec<Action>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn controller(&self) -> Box<dyn Controller> {
+        match self {
+            Difficulty::Easy => Box::new(RandomWalker),
+            Difficulty::Medium => Box::new(Chaser),
+            Difficulty::Hard => Box::new(Kiter),
+        }
+    }
+}
+
+/// Walks and shoots in random directions.
+pub struct RandomWalker;
+
+/// Goes straight to the nearest enemy and shoots as soon as it is on the firing line.
+pub struct Chaser;
+
+/// Keeps the nearest enemy at the bullet range and runs away after every shot.
+pub struct Kiter;
+
+impl Controller for RandomWalker {
+    fn actions(&mut self, info: &GameInfo, actions_count: usize) -> Vec<Action> {
+        let mut rng = rand::thread_rng();
+        let direction = *DIRECTIONS.choose(&mut rng).unwrap();
+        let gun_action = match rng.gen_bool(0.5) {
+            true => DIRECTIONS.choose(&mut rng).copied(),
+            false => None,
+        };
+        plan(
+            actions_count,
+            Action::Move {
+                direction,
+                range: 1,
+            },
+            match info.my_player() {
+                Some(me) => gun(
+                    &me.character,
+                    gun_action.map(|direction| Action::Attack { direction }),
+                ),
+                None => Action::Nothing,
+            },
+        )
+    }
+}
+
+impl Controller for Chaser {
+    fn actions(&mut self, info: &GameInfo, actions_count: usize) -> Vec<Action> {
+        let character = match info.my_player() {
+            Some(me) => &me.character,
+            None => return RandomWalker.actions(info, actions_count),
+        };
+        let (pos, enemy) = match (character.pos, nearest_enemy(info)) {
+            (Some(pos), Some(enemy)) => (pos, enemy),
+            _ => return RandomWalker.actions(info, actions_count),
+        };
+        if let Some(shot) = shot(info, pos, enemy, character.bullet_range) {
+            if character.gun_reloading_await == 0 {
+                return plan(
+                    actions_count,
+                    gun(character, Some(shot)),
+                    step(info, pos, &towards(pos, enemy)),
+                );
+            }
+        }
+        plan(
+            actions_count,
+            step(info, pos, &towards(pos, enemy)),
+            gun(character, None),
+        )
+    }
+}
+
+impl Controller for Kiter {
+    fn actions(&mut self, info: &GameInfo, actions_count: usize) -> Vec<Action> {
+        let character = match info.my_player() {
+            Some(me) => &me.character,
+            None => return RandomWalker.actions(info, actions_count),
+        };
+        let (pos, enemy) = match (character.pos, nearest_enemy(info)) {
+            (Some(pos), Some(enemy)) => (pos, enemy),
+            _ => return RandomWalker.actions(info, actions_count),
+        };
+        let away = away(pos, enemy);
+        if let Some(shot) = shot(info, pos, enemy, character.bullet_range) {
+            if character.gun_reloading_await == 0 {
+                return plan(
+                    actions_count,
+                    gun(character, Some(shot)),
+                    step(info, pos, &away),
+                );
+            }
+        }
+        if pos.distance(enemy) <= character.bullet_range / 2 {
+            return plan(actions_count, step(info, pos, &away), gun(character, None));
+        }
+        plan(
+            actions_count,
+            step(info, pos, &to_firing_line(pos, enemy)),
+            gun(character, None),
+        )
+    }
+}
+
+fn plan(actions_count: usize, first: Action, second: Action) -> Vec<Action> {
+    let mut actions = vec![first, second];
+    actions.resize(actions_count, Action::Nothing);
+    actions
+}
+
+/// Shoots when the gun is ready and there is a target, otherwise reloads it.
+fn gun(character: &CharacterInfo, shot: Option<Action>) -> Action {
+    if character.gun_reloading_await > 0 {
+        return Action::Reload;
+    }
+    shot.unwrap_or(Action::Nothing)
+}
+
+/// Shot along the firing line or, in the games with free aim, along the aimed line.
+fn shot(info: &GameInfo, pos: Pos, enemy: Pos, range: u8) -> Option<Action> {
+    if let Some(direction) = info.map.firing_line(pos, enemy, range, info.movement) {
+        return Some(Action::Attack { direction });
+    }
+    let path = info.map.aimed_shot_path(pos, enemy, range, &occupied(info));
+    (info.free_aim && path.last() == Some(&enemy)).then_some(Action::AttackAt { target: enemy })
+}
+
+/// Moves in the first possible direction of `directions`.
+fn step(info: &GameInfo, pos: Pos, directions: &[Direction]) -> Action {
+    for direction in directions {
+        if let CanMove::Yes = info.map.can_move(pos, *direction) {
+            return Action::Move {
+                direction: *direction,
+                range: 1,
+            };
+        }
+    }
+    Action::Nothing
+}
+
+fn nearest_enemy(info: &GameInfo) -> Option<Pos> {
+    let me = info.me?;
+    let pos = info.players[me].character.pos?;
+    info.players
+        .iter()
+        .enumerate()
+        .filter(|(ind, pl)| *ind != me && !pl.is_ally(&info.players[me]) && pl.character.health > 0)
+        .filter_map(|(_, pl)| pl.character.pos)
+        .min_by_key(|enemy| pos.distance(*enemy))
+}
+
+fn horizontal(from: Pos, to: Pos) -> Direction {
+    if to.x > from.x {
+        Direction::Right
+    } else {
+        Direction::Left
+    }
+}
+
+fn vertical(from: Pos, to: Pos) -> Direction {
+    if to.y > from.y {
+        Direction::Top
+    } else {
+        Direction::Bottom
+    }
+}
+
+/// Directions to the target, the longest axis goes first.
+fn towards(from: Pos, to: Pos) -> [Direction; 2] {
+    if from.x.abs_diff(to.x) >= from.y.abs_diff(to.y) {
+        [horizontal(from, to), vertical(from, to)]
+    } else {
+        [vertical(from, to), horizontal(from, to)]
+    }
+}
+
+/// Directions that take the target to the same row or column, the shortest axis goes first.
+fn to_firing_line(from: Pos, to: Pos) -> [Direction; 2] {
+    let [long, short] = towards(from, to);
+    if from.x == to.x || from.y == to.y {
+        return [long, short];
+    }
+    [short, long]
+}
+
+fn away(from: Pos, to: Pos) -> [Direction; 4] {
+    let [long, short] = towards(from, to);
+    [long.opposite(), short.opposite(), short, long]
+}
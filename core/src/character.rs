@@ -1,50 +1,196 @@
-use std::{fmt::Display, u8};
+use std::{collections::VecDeque, fmt::Display, u8};
 
 use ez_colorize::ColorizeDisplay;
-use serde::Serialize;
+use protocol::geometry::aim;
+pub use protocol::{
+    class::ClassStats,
+    info::{Boost, BoostKind, CharacterInfo},
+};
 
-use super::{direction::Direction, pos::Pos, traits::Movable};
+use super::{
+    ability::{Ability, Cooldowns, REVEAL_TURNS, SHIELD_AMOUNT},
+    direction::Direction,
+    pickup::PickupKind,
+    pos::Pos,
+    traits::Movable,
+};
 #[derive(Debug, Clone)]
 pub struct Character {
+    pub class: Option<String>,
     pub speed: u8,
     pub pos: Pos,
     pub health: Health,
     pub gun: Gun,
+    pub boosts: Vec<Boost>,
+    pub cooldowns: Cooldowns,
+    /// Damage that the shield still blocks.
+    pub shield: u8,
+    pub reveal_turns: u8,
+    /// Turns that everybody sees the hero in the bushes after a shot.
+    pub exposed_turns: u8,
+    /// Turns until the hero can move out of the mud.
+    pub mud_turns: u8,
 }
 
-#[derive(Serialize, Debug)]
-pub struct CharacterInfo {
-    pub health: u8,
-    pub gun_reloading_await: u8,
-    pub bullet_damage: u8,
-    pub bullet_range: u8,
-    pub pos: Option<Pos>,
-}
+/// A shot shows the shooter hiding in the bushes until the end of the turn.
+pub const SHOT_EXPOSURE_TURNS: u8 = 1;
 
-impl CharacterInfo {
-    pub fn new(c: &Character) -> Self {
+impl From<&Character> for CharacterInfo {
+    fn from(c: &Character) -> Self {
         Self {
+            class: c.class.clone(),
+            speed: c.speed,
             health: c.health.current,
+            max_health: c.health.max,
             gun_reloading_await: c.gun.reload_awaiting,
+            reload_time: c.gun.reload_time,
             bullet_damage: c.gun.bullet.damage,
             bullet_range: c.gun.bullet.range,
             pos: Some(c.pos),
+            boosts: c.boosts.clone(),
+            cooldowns: c.cooldowns,
+            shield: c.shield,
+            reveal_turns: c.reveal_turns,
+            mud_turns: c.mud_turns,
+            concealed: false,
         }
     }
-
-    pub fn without_pos(&mut self) {
-        self.pos = None;
-    }
 }
 
 impl Character {
     pub const fn new(speed: u8, pos: Pos, health: Health, gun: Gun) -> Self {
         Self {
+            class: None,
             speed,
             pos,
             health,
             gun,
+            boosts: Vec::new(),
+            cooldowns: Cooldowns {
+                dash: 0,
+                shield: 0,
+                mine: 0,
+                reveal: 0,
+            },
+            shield: 0,
+            reveal_turns: 0,
+            exposed_turns: 0,
+            mud_turns: 0,
+        }
+    }
+
+    pub fn from_class(class: &ClassStats, pos: Pos) -> Self {
+        let mut character = Self::new(
+            class.speed,
+            pos,
+            Health::new(class.health),
+            Gun::new(
+                class.reload_time,
+                Bullet::new(class.bullet_range, class.bullet_damage),
+            ),
+        );
+        character.class = Some(class.name.clone());
+        character
+    }
+
+    pub fn apply_pickup(&mut self, pickup: PickupKind) {
+        match pickup {
+            PickupKind::Health { amount } => self.health.heal(amount),
+            PickupKind::Ammo => self.gun.reload_instantly(),
+            PickupKind::Speed { bonus, duration } => self.boosts.push(Boost {
+                kind: BoostKind::Speed { bonus },
+                turns_left: duration,
+            }),
+            PickupKind::Damage { bonus, duration } => self.boosts.push(Boost {
+                kind: BoostKind::Damage { bonus },
+                turns_left: duration,
+            }),
+        }
+    }
+
+    /// Back to the game at `pos` with full health, a loaded gun and without boosts.
+    pub fn respawn(&mut self, pos: Pos) {
+        self.pos = pos;
+        self.health.current = self.health.max;
+        self.gun.reload_instantly();
+        self.boosts.clear();
+        self.cooldowns = Cooldowns::default();
+        self.shield = 0;
+        self.reveal_turns = 0;
+        self.exposed_turns = 0;
+        self.mud_turns = 0;
+    }
+
+    /// Starts the cooldown of the ability, `false` while it isn't ready.
+    pub fn use_ability(&mut self, ability: Ability) -> bool {
+        let timer = ability.timer(&mut self.cooldowns);
+        if *timer > 0 {
+            return false;
         }
+        *timer = ability.cooldown();
+        match ability {
+            Ability::Shield => self.shield = SHIELD_AMOUNT,
+            Ability::Reveal => self.reveal_turns = REVEAL_TURNS,
+            Ability::Dash | Ability::Mine => {}
+        }
+        true
+    }
+
+    /// The shield takes the damage first, returns the damage that gets through.
+    pub fn absorb(&mut self, damage: u8) -> u8 {
+        let blocked = self.shield.min(damage);
+        self.shield -= blocked;
+        damage - blocked
+    }
+
+    /// Called at the start of every turn.
+    pub fn abilities_update(&mut self) {
+        for ability in [
+            Ability::Dash,
+            Ability::Shield,
+            Ability::Mine,
+            Ability::Reveal,
+        ] {
+            let timer = ability.timer(&mut self.cooldowns);
+            *timer = timer.saturating_sub(1);
+        }
+        self.reveal_turns = self.reveal_turns.saturating_sub(1);
+        self.exposed_turns = self.exposed_turns.saturating_sub(1);
+        self.mud_turns = self.mud_turns.saturating_sub(1);
+    }
+
+    pub fn boosts_update(&mut self) {
+        for boost in self.boosts.iter_mut() {
+            boost.turns_left = boost.turns_left.saturating_sub(1);
+        }
+        self.boosts.retain(|boost| boost.turns_left > 0);
+    }
+
+    pub fn shoot(&mut self, direction: Direction) -> Option<Bullet> {
+        let damage_bonus = self
+            .boosts
+            .iter()
+            .map(|boost| match boost.kind {
+                BoostKind::Damage { bonus } => bonus,
+                _ => 0,
+            })
+            .fold(0u8, |sum, bonus| sum.saturating_add(bonus));
+        let mut bullet = self.gun.shoot(self.pos, direction)?;
+        bullet.damage = bullet.damage.saturating_add(damage_bonus);
+        self.exposed_turns = SHOT_EXPOSURE_TURNS;
+        Some(bullet)
+    }
+
+    /// Shot along the line to the `target`, the bullet stops on it.
+    pub fn shoot_at(&mut self, target: Pos) -> Option<Bullet> {
+        let mut steps: VecDeque<Direction> = aim(self.pos, target).into();
+        let first = steps.pop_front()?;
+        let mut bullet = self.shoot(first)?;
+        let cells = (steps.len() + 1).min(u8::MAX as usize) as u8;
+        bullet.range = bullet.range.min(cells);
+        bullet.direction = Direction::None;
+        bullet.steps = steps;
+        Some(bullet)
     }
 }
 #[derive(Debug, Clone)]
@@ -62,6 +208,10 @@ impl Health {
         self.current > 0
     }
 
+    pub fn heal(&mut self, amount: u8) {
+        self.current = self.current.saturating_add(amount).min(self.max);
+    }
+
     pub fn get_damage(&mut self, damage: u8) {
         if self.current > damage {
             self.current -= damage;
@@ -90,6 +240,10 @@ impl Gun {
         self.reload_awaiting == 0
     }
 
+    pub fn reload_instantly(&mut self) {
+        self.reload_awaiting = 0;
+    }
+
     pub fn reloading_update(&mut self) {
         if self.can_shoot() {
             return;
@@ -114,18 +268,23 @@ impl Gun {
 #[derive(Clone, Debug)]
 pub struct Bullet {
     pos: Pos,
+    pub owner: Option<u8>,
     pub range: u8,
     pub direction: Direction,
     pub damage: u8,
+    /// Next steps of an aimed shot, they go before the `direction`.
+    steps: VecDeque<Direction>,
 }
 
 impl Bullet {
     pub fn new(range: u8, damage: u8) -> Self {
         Self {
             pos: (0, 0).into(),
+            owner: None,
             range,
             direction: Direction::None,
             damage,
+            steps: VecDeque::new(),
         }
     }
 
@@ -139,6 +298,7 @@ impl Bullet {
         self.range = 0;
         self.damage = 0;
         self.direction = Direction::None;
+        self.steps.clear();
     }
 
     pub fn can_move(&self) -> bool {
@@ -156,6 +316,7 @@ impl Movable for Bullet {
             if let Some(new_pos) = self.pos.shift(direction) {
                 self.range -= 1;
                 self.pos = new_pos;
+                self.steps.pop_front();
                 return;
             }
             self.range = 0;
@@ -167,7 +328,7 @@ impl Movable for Bullet {
     }
 
     fn get_direction(&self) -> Direction {
-        self.direction
+        self.steps.front().copied().unwrap_or(self.direction)
     }
 }
 
@@ -183,7 +344,13 @@ impl super::traits::Movable for Character {
     }
 
     fn get_speed(&self) -> u8 {
-        self.speed
+        self.boosts
+            .iter()
+            .map(|boost| match boost.kind {
+                BoostKind::Speed { bonus } => bonus,
+                _ => 0,
+            })
+            .fold(self.speed, |speed, bonus| speed.saturating_add(bonus))
     }
 }
 
@@ -1,20 +1,34 @@
 use std::fmt::Display;
 
+pub use protocol::info::GameInfo;
+
 use super::{
+    ability::{Ability, Mine, DASH_RANGE, MINE_DAMAGE},
+    bot::Controller,
     character::Bullet,
-    map::{CanMove, Cell, Map},
+    direction::{Direction, Movement},
+    map::{CanMove, Cell, Map, WhyDontCanMove, MUD_TURNS},
+    mode::{Elimination, GameMode, Side},
+    pickup::Pickup,
     player::{Action, DynPlayer, PlayerInfo},
+    pos::Pos,
+    result::{GameResult, Tiebreak},
+    stats::PlayerStats,
     traits::Movable,
+    zone::Zone,
 };
 
 use ez_colorize::ColorizeDisplay;
-use serde::Serialize;
+use protocol::geometry::aim;
 
 pub trait LikeGame {
     fn update(&mut self, actions: Vec<Vec<Action>>);
     fn validate_actions(&self, actions: &mut Vec<Action>);
     fn state(&self) -> &GameState;
     fn get_actions_count(&self) -> usize;
+    fn get_turn(&self) -> u16;
+    /// Game state as the player sees it, `None` when the game is over.
+    fn view(&self, player_id: u8) -> Option<GameInfo>;
 }
 
 impl LikeGame for Game {
@@ -35,22 +49,47 @@ impl LikeGame for Game {
         self.actions_count
     }
 
+    fn get_turn(&self) -> u16 {
+        self.turn
+    }
+
+    fn view(&self, player_id: u8) -> Option<GameInfo> {
+        match self.state {
+            GameState::Continue { .. } | GameState::NotStarted { .. } => {
+                Some(game_info(self, Some(player_id)))
+            }
+            _ => None,
+        }
+    }
+
     fn state(&self) -> &GameState {
         &self.state
     }
 
-    fn update(&mut self, actions: Vec<Vec<Action>>) {
+    fn update(&mut self, mut actions: Vec<Vec<Action>>) {
         match self.state {
             GameState::TimeIsOver { .. } => return,
             GameState::End { .. } => return,
             GameState::Continue { .. } | GameState::NotStarted { .. } => {
+                actions.resize(
+                    self.players.len(),
+                    vec![Action::Nothing; self.actions_count],
+                );
+                self.abilities_update();
+                self.bots_actions(&mut actions);
+                self.turn_stats_update(&actions);
                 for action_ind in 0..self.actions_count {
                     for pl_ind in 0..self.players.len() {
                         self.execute_action(pl_ind, actions[pl_ind][action_ind]);
                     }
                 }
                 self.bullet_update();
+                self.conveyors_update();
+                self.boosts_update();
+                self.pickups_update();
+                self.mines_update();
                 self.time_update();
+                self.zone_update();
 
                 self.game_state_update();
             }
@@ -66,6 +105,17 @@ pub struct Game {
     time_limit: u16,
     bullets: Vec<Bullet>,
     actions_count: usize,
+    pickups: Vec<Pickup>,
+    zone: Option<Zone>,
+    turn: u16,
+    tiebreak: Tiebreak,
+    stats: Vec<PlayerStats>,
+    bots: Vec<(u8, Box<dyn Controller>)>,
+    friendly_fire: bool,
+    mode: Box<dyn GameMode>,
+    mines: Vec<Mine>,
+    movement: Movement,
+    free_aim: bool,
 }
 
 impl Game {
@@ -76,6 +126,10 @@ impl Game {
         actions_count: usize,
     ) -> Self {
         players.sort_by(|f, s| f.get_id().cmp(&s.get_id()));
+        let stats = players
+            .iter()
+            .map(|pl| PlayerStats::new(pl.get_id()))
+            .collect();
         let mut game = Self {
             state: GameState::Empty,
             map,
@@ -83,9 +137,20 @@ impl Game {
             time_limit,
             bullets: Vec::new(),
             actions_count,
+            pickups: Vec::new(),
+            zone: None,
+            turn: 0,
+            tiebreak: Tiebreak::default(),
+            stats,
+            bots: Vec::new(),
+            friendly_fire: false,
+            mode: Box::new(Elimination),
+            mines: Vec::new(),
+            movement: Movement::default(),
+            free_aim: false,
         };
         game.state = GameState::NotStarted {
-            info: GameInfo::new(&game),
+            info: game_info(&game, None),
         };
         game
     }
@@ -93,39 +158,79 @@ impl Game {
     fn check_time_limit_over(&mut self) {
         if self.time_limit == 0 {
             self.state = GameState::TimeIsOver {
-                winners: self.alives(),
+                result: self.result(),
             };
         }
     }
 
     fn time_update(&mut self) {
         self.time_limit -= 1;
+        self.turn += 1;
+    }
+
+    fn zone_update(&mut self) {
+        if let Some(zone) = self.zone.as_mut() {
+            zone.update(self.turn);
+            for (ind, pl) in self.players.iter_mut().enumerate() {
+                if pl.alive() && !zone.contains(pl.get_position()) {
+                    let health = pl.character().health.current;
+                    let damage = pl.character_mut().absorb(zone.get_damage());
+                    pl.get_damage(damage);
+                    self.stats[ind].got_damage(health, damage);
+                }
+            }
+        }
     }
 
     fn game_state_update(&mut self) {
+        let over = self.mode.update(&mut self.players, &self.stats);
         if let GameState::Continue { .. } | GameState::NotStarted { .. } = self.state {
             self.state = GameState::Continue {
-                info: GameInfo::new(self),
+                info: game_info(self, None),
             };
         }
-        self.have_winner();
+        if over {
+            self.state = GameState::End {
+                result: self.result(),
+            }
+        }
         self.check_time_limit_over();
     }
 
-    fn alives(&self) -> Vec<u8> {
-        let mut alives = Vec::new();
-        for pl in self.players.iter() {
-            if pl.alive() {
-                alives.push(pl.get_id());
-            }
+    fn score(&self, player_ind: usize) -> u16 {
+        if let Some(score) = self.mode.score(self.players[player_ind].get_id()) {
+            return score;
+        }
+        let stats = &self.stats[player_ind];
+        match self.tiebreak {
+            Tiebreak::Health => self.players[player_ind].character().health.current as u16,
+            Tiebreak::DamageDealt => stats.damage_dealt,
+            Tiebreak::Kills => stats.kills,
+            Tiebreak::Draw => 0,
         }
-        return alives;
     }
 
-    fn have_winner(&mut self) {
-        let alives = self.alives();
-        if alives.len() <= 1 {
-            self.state = GameState::End { winners: alives }
+    fn result(&self) -> GameResult {
+        let result = GameResult::new(
+            self.players
+                .iter()
+                .enumerate()
+                .map(|(ind, pl)| {
+                    let alive = pl.alive() || self.mode.respawns();
+                    (pl.get_id(), alive, self.score(ind))
+                })
+                .collect(),
+            self.stats.clone(),
+        );
+        let members: Vec<(u8, u8)> = self
+            .players
+            .iter()
+            .filter_map(|pl| Some((pl.get_id(), pl.get_team()?)))
+            .collect();
+        if members.is_empty() {
+            result
+        } else {
+            result.with_teams(&members)
         }
     }
 
@@ -137,21 +242,56 @@ impl Game {
         } else {
             return;
         }
+        let teams: Vec<(u8, Option<u8>)> = self
+            .players
+            .iter()
+            .map(|p| (p.get_id(), p.get_team()))
+            .collect();
         for _ in 0..count {
             '_move: for b in self.bullets.iter_mut() {
                 if b.can_move() {
                     let bullet_pos = b.get_position();
+                    let owner_team = b
+                        .owner
+                        .and_then(|owner| teams.iter().find(|(id, _)| *id == owner))
+                        .and_then(|(_, team)| *team);
                     for p in self.players.iter_mut() {
-                        if p.get_position() == bullet_pos {
-                            p.get_damage(b.use_up());
+                        // Without friendly fire bullets fly through the allies
+                        if !self.friendly_fire && owner_team.is_some() && p.get_team() == owner_team
+                        {
+                            continue;
+                        }
+                        if p.alive() && p.get_position() == bullet_pos {
+                            let health = p.character().health.current;
+                            let damage = p.character_mut().absorb(b.use_up());
+                            p.get_damage(damage);
+                            if let Some(stats) = self.stats.iter_mut().find(|s| s.id == p.get_id())
+                            {
+                                stats.got_damage(health, damage);
+                            }
+                            if let Some(stats) = b
+                                .owner
+                                .and_then(|owner| self.stats.iter_mut().find(|s| s.id == owner))
+                            {
+                                stats.hit(health, damage, self.turn);
+                            }
                             continue '_move;
                         }
                     }
                     let bullet_direction = b.get_direction();
-                    match self.map.can_move(bullet_pos, bullet_direction) {
+                    match self.map.can_fly(bullet_pos, bullet_direction) {
                         CanMove::Yes => {
                             b.shift(bullet_direction);
                         }
+                        CanMove::No(WhyDontCanMove::ImpassableObject {
+                            obj: Cell::DestructibleWall { .. },
+                        }) => {
+                            if let Some(wall_pos) =
+                                self.map.bullet_obstacle(bullet_pos, bullet_direction)
+                            {
+                                self.map.damage_cell(wall_pos, b.use_up());
+                            }
+                        }
                         CanMove::No(..) => {
                             continue '_move;
                         }
@@ -162,23 +302,198 @@ impl Game {
         self.bullets.clear();
     }
 
+    /// Replaces the actions of the players driven by the controllers.
+    fn bots_actions(&mut self, actions: &mut [Vec<Action>]) {
+        if self.bots.is_empty() {
+            return;
+        }
+        let views: Vec<GameInfo> = self
+            .bots
+            .iter()
+            .map(|(id, _)| game_info(self, Some(*id)))
+            .collect();
+        for ((_, controller), info) in self.bots.iter_mut().zip(views) {
+            if let Some(ind) = info.me {
+                let mut bot_actions = controller.actions(&info, self.actions_count);
+                bot_actions.resize(self.actions_count, Action::Nothing);
+                actions[ind] = bot_actions;
+            }
+        }
+    }
+
+    /// The conveyors move the living players on them by one cell.
+    fn conveyors_update(&mut self) {
+        for pl in self.players.iter_mut().filter(|pl| pl.alive()) {
+            if let Cell::Conveyor { direction } = self.map.get_cell(pl.get_position()) {
+                walk(&self.map, pl.as_mut(), direction, 1);
+            }
+        }
+    }
+
+    fn boosts_update(&mut self) {
+        for pl in self.players.iter_mut() {
+            pl.character_mut().boosts_update();
+        }
+    }
+
+    fn abilities_update(&mut self) {
+        for pl in self.players.iter_mut() {
+            pl.character_mut().abilities_update();
+        }
+    }
+
+    /// Counts the turns of the living players with a reload and the turns without any action.
+    fn turn_stats_update(&mut self, actions: &[Vec<Action>]) {
+        for (pl_ind, pl) in self.players.iter().enumerate() {
+            if !pl.alive() {
+                continue;
+            }
+            let stats = &mut self.stats[pl_ind];
+            if actions[pl_ind].contains(&Action::Reload) {
+                stats.reloading_turns += 1;
+            }
+            if actions[pl_ind]
+                .iter()
+                .all(|action| *action == Action::Nothing)
+            {
+                stats.idle_turns += 1;
+            }
+        }
+    }
+
+    /// A mine explodes under the first living enemy of the owner on its cell.
+    fn mines_update(&mut self) {
+        let mut exploded = Vec::new();
+        for (mine_ind, mine) in self.mines.iter().enumerate() {
+            let owner_side = self
+                .players
+                .iter()
+                .find(|pl| pl.get_id() == mine.owner)
+                .map(|pl| Side::of(pl.as_ref()));
+            let target = self.players.iter().position(|pl| {
+                pl.alive()
+                    && pl.get_position() == mine.pos
+                    && Some(Side::of(pl.as_ref())) != owner_side
+            });
+            if let Some(ind) = target {
+                let pl = &mut self.players[ind];
+                let health = pl.character().health.current;
+                let damage = pl.character_mut().absorb(mine.damage);
+                pl.get_damage(damage);
+                self.stats[ind].got_damage(health, damage);
+                if let Some(stats) = self.stats.iter_mut().find(|s| s.id == mine.owner) {
+                    stats.hit(health, damage, self.turn);
+                }
+                exploded.push(mine_ind);
+            }
+        }
+        for ind in exploded.into_iter().rev() {
+            self.mines.remove(ind);
+        }
+    }
+
+    fn pickups_update(&mut self) {
+        for pickup in self.pickups.iter_mut() {
+            pickup.respawn_update();
+            for pl in self.players.iter_mut() {
+                if pl.alive() && pl.get_position() == pickup.pos {
+                    if let Some(kind) = pickup.pick_up() {
+                        pl.character_mut().apply_pickup(kind);
+                    }
+                }
+            }
+        }
+    }
+
     fn execute_action(&mut self, player_ind: usize, action: Action) {
         let player = &mut self.players[player_ind];
-        let pos = player.get_position();
+        let stats = &mut self.stats[player_ind];
+        if !player.alive() {
+            return;
+        }
+        if let Action::Attack { direction }
+        | Action::Move { direction, .. }
+        | Action::Dash { direction } = action
+        {
+            if !self.movement.allows(direction) {
+                stats.invalid_actions += 1;
+                return;
+            }
+        }
         match action {
             Action::Attack { direction } => {
                 if let Some(bullet) = player.attack(direction) {
-                    self.bullets.push(bullet);
+                    stats.shots_fired += 1;
+                    let from = player.get_position();
+                    launch(&mut self.map, &mut self.bullets, from, direction, bullet);
+                } else {
+                    stats.invalid_actions += 1;
                 }
             }
-            Action::Move { direction, range } => {
-                for _ in 0..player.get_speed().min(range) {
-                    match self.map.can_move(pos, direction) {
-                        CanMove::Yes => {
-                            player.shift(direction);
+            Action::AttackAt { target } => {
+                let from = player.get_position();
+                let first = aim(from, target).first().copied();
+                match (self.free_aim, first) {
+                    (true, Some(direction)) => match player.attack_at(target) {
+                        Some(bullet) => {
+                            stats.shots_fired += 1;
+                            launch(&mut self.map, &mut self.bullets, from, direction, bullet);
                         }
-                        CanMove::No(_) => return,
-                    }
+                        None => stats.invalid_actions += 1,
+                    },
+                    _ => stats.invalid_actions += 1,
+                }
+            }
+            Action::Move { direction, range } => {
+                let stuck = player.character().mud_turns > 0
+                    && matches!(self.map.get_cell(player.get_position()), Cell::Mud);
+                let cells = match stuck {
+                    true => 0,
+                    false => player.get_speed().min(range),
+                };
+                let moved = walk(&self.map, player.as_mut(), direction, cells);
+                if moved > 0 && matches!(self.map.get_cell(player.get_position()), Cell::Mud) {
+                    player.character_mut().mud_turns = MUD_TURNS;
+                }
+                if moved == 0 {
+                    stats.invalid_actions += 1;
+                }
+                stats.distance_moved += moved;
+            }
+            Action::Dash { direction } => {
+                let mut moved = 0;
+                // A dash that can't move keeps the ability ready
+                let can_move = matches!(
+                    self.map.can_move(player.get_position(), direction),
+                    CanMove::Yes
+                );
+                if can_move && player.character_mut().use_ability(Ability::Dash) {
+                    moved = walk(&self.map, player.as_mut(), direction, DASH_RANGE);
+                }
+                if moved == 0 {
+                    stats.invalid_actions += 1;
+                }
+                stats.distance_moved += moved;
+            }
+            Action::Shield => {
+                if !player.character_mut().use_ability(Ability::Shield) {
+                    stats.invalid_actions += 1;
+                }
+            }
+            Action::Reveal => {
+                if !player.character_mut().use_ability(Ability::Reveal) {
+                    stats.invalid_actions += 1;
+                }
+            }
+            Action::PlaceMine => {
+                if player.character_mut().use_ability(Ability::Mine) {
+                    self.mines.push(Mine {
+                        pos: player.get_position(),
+                        owner: player.get_id(),
+                        damage: MINE_DAMAGE,
+                    });
+                } else {
+                    stats.invalid_actions += 1;
                 }
             }
             Action::Reload => player.reloading(),
@@ -187,18 +502,6 @@ impl Game {
     }
 }
 
-#[derive(Serialize)]
-pub struct GameInfo {
-    map: Map,
-    players: Vec<PlayerInfo>,
-}
-
-impl Display for GameInfo {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.map)
-    }
-}
-
 impl Display for GameState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -208,14 +511,19 @@ impl Display for GameState {
             GameState::Continue { info } => {
                 write!(f, "{}, state:\n{}", "Game continue".green(), info)
             }
-            GameState::TimeIsOver { winners } => {
-                write!(f, "{}, winners id: {:?}", "Time is over".green(), winners)
+            GameState::TimeIsOver { result } => {
+                write!(
+                    f,
+                    "{}, winners id: {:?}",
+                    "Time is over".green(),
+                    result.winners
+                )
             }
-            GameState::End { winners } => writeln!(
+            GameState::End { result } => writeln!(
                 f,
                 "{}, winners id: {:?}",
                 "End of game with winners".green(),
-                winners
+                result.winners
             ),
             GameState::Empty => write!(f, "No state, empty"),
         }
@@ -224,15 +532,31 @@ impl Display for GameState {
 
 #[derive(Default)]
 pub struct GameBuilder {
+    map: Option<Map>,
     map_size: Option<u8>,
     players: Vec<Box<dyn DynPlayer>>,
     time_limit: Option<u16>,
     actions_count: Option<usize>,
+    pickups: Vec<Pickup>,
+    zone: Option<(u16, u16, u8)>,
+    tiebreak: Tiebreak,
+    bots: Vec<(u8, Box<dyn Controller>)>,
+    friendly_fire: bool,
+    mode: Option<Box<dyn GameMode>>,
+    movement: Movement,
+    free_aim: bool,
 }
 
 impl GameBuilder {
     pub fn add_players(mut self, players: Vec<Box<dyn DynPlayer>>) -> Self {
-        self.players = players;
+        self.players.extend(players);
+        self
+    }
+
+    /// Adds a player driven by the server side controller.
+    pub fn add_bot(mut self, bot: Box<dyn DynPlayer>, controller: Box<dyn Controller>) -> Self {
+        self.bots.push((bot.get_id(), controller));
+        self.players.push(bot);
         self
     }
 
@@ -251,37 +575,176 @@ impl GameBuilder {
         self
     }
 
+    pub fn add_pickups(mut self, pickups: Vec<Pickup>) -> Self {
+        self.pickups = pickups;
+        self
+    }
+
+    /// Safe zone starts shrinking at `start_turn` by one cell from every side each
+    /// `shrink_every` turns, players outside of it get `damage` every turn.
+    pub fn add_zone(mut self, start_turn: u16, shrink_every: u16, damage: u8) -> Self {
+        self.zone = Some((start_turn, shrink_every, damage));
+        self
+    }
+
+    pub fn add_tiebreak(mut self, tiebreak: Tiebreak) -> Self {
+        self.tiebreak = tiebreak;
+        self
+    }
+
+    /// In team games the players hurt their allies only with friendly fire.
+    pub fn add_friendly_fire(mut self, friendly_fire: bool) -> Self {
+        self.friendly_fire = friendly_fire;
+        self
+    }
+
+    /// Objective of the match, elimination by default.
+    pub fn add_mode(mut self, mode: Box<dyn GameMode>) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// With `Movement::EightWay` the players move, dash and shoot diagonally too.
+    pub fn add_movement(mut self, movement: Movement) -> Self {
+        self.movement = movement;
+        self
+    }
+
+    /// With free aim the players shoot at any cell by `Action::AttackAt`.
+    pub fn add_free_aim(mut self, free_aim: bool) -> Self {
+        self.free_aim = free_aim;
+        self
+    }
+
+    pub fn add_map(mut self, map: Map) -> Self {
+        self.map = Some(map);
+        self
+    }
+
     pub fn build(self) -> Box<dyn LikeGame> {
-        Box::new(Game::new(
-            Map::new_empty(self.map_size.unwrap_or(5)),
+        let map = self
+            .map
+            .unwrap_or_else(|| Map::new_empty(self.map_size.unwrap_or(5)));
+        let zone = self.zone.map(|(start_turn, shrink_every, damage)| {
+            Zone::new(map.get_size(), start_turn, shrink_every, damage)
+        });
+        let mut game = Game::new(
+            map,
             self.players,
             self.time_limit.unwrap_or(1000),
             self.actions_count.unwrap_or(2),
-        ))
+        );
+        game.pickups = self.pickups;
+        game.zone = zone;
+        game.tiebreak = self.tiebreak;
+        game.bots = self.bots;
+        game.friendly_fire = self.friendly_fire;
+        game.movement = self.movement;
+        game.free_aim = self.free_aim;
+        if let Some(mode) = self.mode {
+            game.mode = mode;
+        }
+        game.state = GameState::NotStarted {
+            info: game_info(&game, None),
+        };
+        Box::new(game)
+    }
+}
+
+/// Adds the bullet that appeared on the cell next to `from`, an impassable cell on that step
+/// (a corner of a diagonal too) stops it right away.
+fn launch(
+    map: &mut Map,
+    bullets: &mut Vec<Bullet>,
+    from: Pos,
+    direction: Direction,
+    bullet: Bullet,
+) {
+    match map.bullet_obstacle(from, direction) {
+        Some(obstacle) => map.damage_cell(obstacle, bullet.damage),
+        None => bullets.push(bullet),
     }
 }
 
-impl GameInfo {
-    fn new(game: &Game) -> Self {
-        let mut players = Vec::new();
-        for pl in game.players.iter() {
-            let mut info = PlayerInfo::new(pl.character());
-            if let Cell::Bushes = game.map.get_cell(pl.get_position()) {
-                info.without_pos();
+/// Moves the player up to `cells` cells until an obstacle, a teleport or the mud, returns
+/// the cells passed.
+fn walk(map: &Map, player: &mut dyn DynPlayer, direction: Direction, cells: u8) -> u16 {
+    let mut moved = 0;
+    for _ in 0..cells {
+        match map.can_move(player.get_position(), direction) {
+            CanMove::Yes => {
+                player.shift(direction);
+                moved += 1;
             }
-            players.push(info);
+            CanMove::No(_) => break,
         }
-        Self {
-            map: game.map.clone(),
-            players,
+        if let Some(exit) = map.teleport_exit(player.get_position()) {
+            player.character_mut().pos = exit;
+            break;
+        }
+        if let Cell::Mud = map.get_cell(player.get_position()) {
+            break;
         }
     }
+    moved
+}
+
+/// A living player in the bushes is concealed from the enemies of the `viewer`, unless one of
+/// them stands next to it, it fired this turn or a living player of the viewer side has an
+/// active reveal. The viewer and the allies of the viewer are always seen. Only the viewer side
+/// sees its mines.
+fn game_info(game: &Game, viewer: Option<u8>) -> GameInfo {
+    let side_of = |id: u8| {
+        game.players
+            .iter()
+            .find(|pl| pl.get_id() == id)
+            .map(|pl| Side::of(pl.as_ref()))
+    };
+    let viewer_side = viewer.and_then(side_of);
+    let watchers: Vec<&dyn DynPlayer> = game
+        .players
+        .iter()
+        .map(|pl| pl.as_ref())
+        .filter(|pl| pl.alive() && viewer_side == Some(Side::of(*pl)))
+        .collect();
+    let revealed = watchers.iter().any(|pl| pl.character().reveal_turns > 0);
+    let mut players = Vec::new();
+    for pl in game.players.iter() {
+        let mut info = PlayerInfo::from(pl.character());
+        info.team = pl.get_team();
+        let pos = pl.get_position();
+        let seen = revealed
+            || !pl.alive()
+            || pl.character().exposed_turns > 0
+            || viewer_side == Some(Side::of(pl.as_ref()))
+            || watchers.iter().any(|w| w.get_position().adjacent(pos));
+        if let (Cell::Bushes, false) = (game.map.get_cell(pos), seen) {
+            info.conceal();
+        }
+        players.push(info);
+    }
+    GameInfo {
+        map: game.map.clone(),
+        players,
+        pickups: game.pickups.clone(),
+        zone: game.zone.as_ref().map(|zone| zone.info(game.turn)),
+        objective: game.mode.info(),
+        mines: game
+            .mines
+            .iter()
+            .filter(|mine| viewer_side.is_some() && side_of(mine.owner) == viewer_side)
+            .copied()
+            .collect(),
+        movement: game.movement,
+        free_aim: game.free_aim,
+        me: viewer.and_then(|id| game.players.iter().position(|pl| pl.get_id() == id)),
+    }
 }
 
 pub enum GameState {
-    End { winners: Vec<u8> },
+    End { result: GameResult },
     NotStarted { info: GameInfo },
-    TimeIsOver { winners: Vec<u8> },
+    TimeIsOver { result: GameResult },
     Continue { info: GameInfo },
     Empty,
 }
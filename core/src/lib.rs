@@ -1,7 +1,14 @@
+pub mod ability;
+pub mod bot;
 pub mod character;
 pub mod direction;
 pub mod game;
-mod map;
+pub mod map;
+pub mod mode;
+pub mod pickup;
 pub mod player;
 pub mod pos;
+pub mod result;
+pub mod stats;
 pub mod traits;
+pub mod zone;
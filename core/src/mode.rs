@@ -0,0 +1,358 @@
+pub use protocol::objective::{Flag, KillDeath, ObjectiveInfo, Points, Respawn};
+
+use super::{player::DynPlayer, pos::Pos, stats::PlayerStats, zone::Bounds};
+
+/// Objective of the match, decides when it is over instead of the last survivor rule.
+pub trait GameMode {
+    /// Called at the end of every turn, returns `true` when the match is over.
+    fn update(&mut self, players: &mut [Box<dyn DynPlayer>], stats: &[PlayerStats]) -> bool;
+    /// Dead players come back, so the result ranks everybody as alive.
+    fn respawns(&self) -> bool {
+        false
+    }
+    /// Score of the player in the result, `None` leaves the tiebreak score.
+    fn score(&self, _player_id: u8) -> Option<u16> {
+        None
+    }
+    /// What the clients know about the objective.
+    fn info(&self) -> Option<ObjectiveInfo> {
+        None
+    }
+}
+
+/// Who plays together: a team or a single player in free-for-all.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Side {
+    Team(u8),
+    Solo(u8),
+}
+
+impl Side {
+    pub fn of(player: &dyn DynPlayer) -> Self {
+        match player.get_team() {
+            Some(team) => Side::Team(team),
+            None => Side::Solo(player.get_id()),
+        }
+    }
+}
+
+/// Sides that still have living players.
+pub fn alive_sides(players: &[Box<dyn DynPlayer>]) -> Vec<Side> {
+    let mut sides = Vec::new();
+    for pl in players.iter() {
+        let side = Side::of(pl.as_ref());
+        if pl.alive() && !sides.contains(&side) {
+            sides.push(side);
+        }
+    }
+    sides
+}
+
+/// The last side standing wins.
+pub struct Elimination;
+
+impl GameMode for Elimination {
+    fn update(&mut self, players: &mut [Box<dyn DynPlayer>], _stats: &[PlayerStats]) -> bool {
+        alive_sides(players).len() <= 1
+    }
+}
+
+/// A player takes an enemy flag by stepping on it and captures it by bringing it to the base
+/// of the own flag while the own flag is there. A dropped flag stays where its carrier died
+/// until an enemy takes it or an ally returns it to the base by stepping on it.
+/// Elimination still ends the match, the players without a team don't play for flags.
+pub struct CaptureTheFlag {
+    flags: Vec<Flag>,
+    captures: Vec<Points>,
+    /// Captures of every player, they are the score in the result.
+    scores: Vec<Points>,
+    captures_to_win: u16,
+}
+
+impl CaptureTheFlag {
+    /// One flag for every `(team, base)`.
+    pub fn new(bases: &[(u8, Pos)], captures_to_win: u16) -> Self {
+        Self {
+            flags: bases
+                .iter()
+                .map(|&(team, base)| Flag {
+                    team,
+                    base,
+                    pos: base,
+                    carrier: None,
+                })
+                .collect(),
+            captures: bases
+                .iter()
+                .map(|&(team, _)| Points {
+                    id: team,
+                    points: 0,
+                })
+                .collect(),
+            scores: Vec::new(),
+            captures_to_win: captures_to_win.max(1),
+        }
+    }
+
+    fn carries(&self, player_id: u8) -> bool {
+        self.flags
+            .iter()
+            .any(|flag| flag.carrier == Some(player_id))
+    }
+
+    fn capture(&mut self, team: u8, player_id: u8) {
+        if let Some(captures) = self.captures.iter_mut().find(|c| c.id == team) {
+            captures.points += 1;
+        }
+        match self.scores.iter_mut().find(|s| s.id == player_id) {
+            Some(score) => score.points += 1,
+            None => self.scores.push(Points {
+                id: player_id,
+                points: 1,
+            }),
+        }
+    }
+}
+
+impl GameMode for CaptureTheFlag {
+    fn update(&mut self, players: &mut [Box<dyn DynPlayer>], _stats: &[PlayerStats]) -> bool {
+        for flag in self.flags.iter_mut() {
+            if let Some(carrier) = flag
+                .carrier
+                .and_then(|id| players.iter().find(|pl| pl.get_id() == id))
+            {
+                flag.pos = carrier.get_position();
+                if !carrier.alive() {
+                    flag.carrier = None;
+                }
+            }
+        }
+        for pl in players.iter().filter(|pl| pl.alive()) {
+            let (id, pos) = (pl.get_id(), pl.get_position());
+            let team = match pl.get_team() {
+                Some(team) => team,
+                None => continue,
+            };
+            for ind in 0..self.flags.len() {
+                let flag = self.flags[ind];
+                if flag.carrier.is_some() || flag.pos != pos {
+                    continue;
+                }
+                if flag.team == team {
+                    self.flags[ind].pos = flag.base;
+                } else if !self.carries(id) {
+                    self.flags[ind].carrier = Some(id);
+                }
+            }
+            let home = self
+                .flags
+                .iter()
+                .any(|flag| flag.team == team && flag.at_base() && flag.base == pos);
+            if let Some(ind) = self.flags.iter().position(|flag| flag.carrier == Some(id)) {
+                if home {
+                    let flag = &mut self.flags[ind];
+                    flag.carrier = None;
+                    flag.pos = flag.base;
+                    self.capture(team, id);
+                }
+            }
+        }
+        self.captures
+            .iter()
+            .any(|captures| captures.points >= self.captures_to_win)
+            || alive_sides(players).len() <= 1
+    }
+
+    fn score(&self, player_id: u8) -> Option<u16> {
+        let score = self.scores.iter().find(|score| score.id == player_id);
+        Some(score.map_or(0, |score| score.points))
+    }
+
+    fn info(&self) -> Option<ObjectiveInfo> {
+        Some(ObjectiveInfo::CaptureTheFlag {
+            flags: self.flags.clone(),
+            captures: self.captures.clone(),
+            captures_to_win: self.captures_to_win,
+        })
+    }
+}
+
+/// Every living player in the hill earns a point per turn if there are no enemies in it.
+/// A side wins when the points of its players reach `points_to_win`,
+/// elimination still ends the match.
+pub struct KingOfTheHill {
+    hill: Bounds,
+    points: Vec<Points>,
+    points_to_win: u16,
+}
+
+impl KingOfTheHill {
+    pub fn new(hill: Bounds, points_to_win: u16) -> Self {
+        Self {
+            hill,
+            points: Vec::new(),
+            points_to_win: points_to_win.max(1),
+        }
+    }
+
+    fn points(&self, player_id: u8) -> u16 {
+        self.points
+            .iter()
+            .find(|points| points.id == player_id)
+            .map_or(0, |points| points.points)
+    }
+}
+
+impl GameMode for KingOfTheHill {
+    fn update(&mut self, players: &mut [Box<dyn DynPlayer>], _stats: &[PlayerStats]) -> bool {
+        let holders: Vec<&dyn DynPlayer> = players
+            .iter()
+            .map(|pl| pl.as_ref())
+            .filter(|pl| pl.alive() && self.hill.contains(pl.get_position()))
+            .collect();
+        let sides: Vec<Side> = holders.iter().map(|pl| Side::of(*pl)).collect();
+        if sides.windows(2).all(|pair| pair[0] == pair[1]) {
+            for pl in holders {
+                match self.points.iter_mut().find(|p| p.id == pl.get_id()) {
+                    Some(points) => points.points += 1,
+                    None => self.points.push(Points {
+                        id: pl.get_id(),
+                        points: 1,
+                    }),
+                }
+            }
+        }
+        let mut totals: Vec<(Side, u16)> = Vec::new();
+        for pl in players.iter() {
+            let side = Side::of(pl.as_ref());
+            let points = self.points(pl.get_id());
+            match totals.iter_mut().find(|(s, _)| *s == side) {
+                Some((_, total)) => *total += points,
+                None => totals.push((side, points)),
+            }
+        }
+        totals.iter().any(|(_, total)| *total >= self.points_to_win)
+            || alive_sides(players).len() <= 1
+    }
+
+    fn score(&self, player_id: u8) -> Option<u16> {
+        Some(self.points(player_id))
+    }
+
+    fn info(&self) -> Option<ObjectiveInfo> {
+        Some(ObjectiveInfo::KingOfTheHill {
+            hill: self.hill,
+            points: self.points.clone(),
+            points_to_win: self.points_to_win,
+        })
+    }
+}
+
+/// Killed players come back after `respawn_turns` at the spawn point farthest from the living
+/// enemies. A side wins when the kills of its players reach `kills_to_win`, otherwise the match
+/// lasts until the time limit.
+pub struct Deathmatch {
+    spawns: Vec<Pos>,
+    respawn_turns: u8,
+    kills_to_win: u16,
+    respawns: Vec<Respawn>,
+    scores: Vec<KillDeath>,
+}
+
+impl Deathmatch {
+    pub fn new(spawns: Vec<Pos>, respawn_turns: u8, kills_to_win: u16) -> Self {
+        Self {
+            spawns,
+            respawn_turns,
+            kills_to_win: kills_to_win.max(1),
+            respawns: Vec::new(),
+            scores: Vec::new(),
+        }
+    }
+
+    /// Free spawn point with the largest distance to the nearest living enemy.
+    fn spawn_point(&self, players: &[Box<dyn DynPlayer>], ind: usize) -> Option<Pos> {
+        let side = Side::of(players[ind].as_ref());
+        let distance = |pos: Pos| {
+            players
+                .iter()
+                .filter(|pl| pl.alive() && Side::of(pl.as_ref()) != side)
+                .map(|pl| {
+                    let enemy = pl.get_position();
+                    enemy.x.abs_diff(pos.x) as u16 + enemy.y.abs_diff(pos.y) as u16
+                })
+                .min()
+                .unwrap_or(u16::MAX)
+        };
+        self.spawns
+            .iter()
+            .copied()
+            .filter(|pos| {
+                !players
+                    .iter()
+                    .any(|pl| pl.alive() && pl.get_position() == *pos)
+            })
+            .max_by_key(|pos| distance(*pos))
+    }
+}
+
+impl GameMode for Deathmatch {
+    fn update(&mut self, players: &mut [Box<dyn DynPlayer>], stats: &[PlayerStats]) -> bool {
+        for respawn in self.respawns.iter_mut() {
+            respawn.turns_left = respawn.turns_left.saturating_sub(1);
+        }
+        for ind in 0..players.len() {
+            let id = players[ind].get_id();
+            let waiting = self.respawns.iter().position(|r| r.id == id);
+            match waiting {
+                Some(waiting) if self.respawns[waiting].turns_left == 0 => {
+                    // Without a free spawn point the player waits one more turn
+                    if let Some(pos) = self.spawn_point(players, ind) {
+                        players[ind].character_mut().respawn(pos);
+                        self.respawns.remove(waiting);
+                    }
+                }
+                None if !players[ind].alive() => self.respawns.push(Respawn {
+                    id,
+                    turns_left: self.respawn_turns,
+                }),
+                _ => {}
+            }
+        }
+        self.scores = stats
+            .iter()
+            .map(|stats| KillDeath {
+                id: stats.id,
+                kills: stats.kills,
+                deaths: stats.deaths,
+            })
+            .collect();
+        let mut totals: Vec<(Side, u16)> = Vec::new();
+        for pl in players.iter() {
+            let side = Side::of(pl.as_ref());
+            let kills = self.score(pl.get_id()).unwrap_or(0);
+            match totals.iter_mut().find(|(s, _)| *s == side) {
+                Some((_, total)) => *total += kills,
+                None => totals.push((side, kills)),
+            }
+        }
+        totals.iter().any(|(_, total)| *total >= self.kills_to_win)
+    }
+
+    fn respawns(&self) -> bool {
+        true
+    }
+
+    fn score(&self, player_id: u8) -> Option<u16> {
+        let score = self.scores.iter().find(|score| score.id == player_id);
+        Some(score.map_or(0, |score| score.kills))
+    }
+
+    fn info(&self) -> Option<ObjectiveInfo> {
+        Some(ObjectiveInfo::Deathmatch {
+            scores: self.scores.clone(),
+            respawns: self.respawns.clone(),
+            kills_to_win: self.kills_to_win,
+        })
+    }
+}
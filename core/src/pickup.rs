@@ -0,0 +1 @@
+pub use protocol::pickup::{Pickup, PickupKind};
@@ -1,30 +1,20 @@
-use std::fmt::Display;
+pub use protocol::{info::PlayerInfo, message::Action};
 
 use super::{
-    character::{Bullet, Character, CharacterInfo, Gun, Health},
+    character::{Bullet, Character, Gun, Health},
     direction::Direction,
     pos::Pos,
-    traits::{Attack, Movable, WithCharacter, WithHealth, WithId},
+    traits::{Attack, Movable, WithCharacter, WithHealth, WithId, WithTeam},
 };
 
-pub trait DynPlayer: WithHealth + WithId + Attack + Movable + WithCharacter {}
+pub trait DynPlayer: WithHealth + WithId + WithTeam + Attack + Movable + WithCharacter {}
 
 #[derive(Debug)]
 pub struct Player {
     id: u8,
     typ: PlayerType,
     character: Character,
-}
-
-use serde::{Deserialize, Serialize};
-
-#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
-#[serde(tag = "action")]
-pub enum Action {
-    Move { direction: Direction, range: u8 },
-    Attack { direction: Direction },
-    Reload,
-    Nothing,
+    team: Option<u8>,
 }
 
 impl Player {
@@ -33,18 +23,29 @@ impl Player {
             id,
             typ: PlayerType::Player,
             character,
+            team: None,
         }
     }
 
+    pub fn with_team(mut self, team: u8) -> Self {
+        self.team = Some(team);
+        self
+    }
+
     pub fn new_default_player(id: u8, position: Pos) -> Self {
-        Self::new_player(
-            id,
-            Character::new(
-                1,
-                position,
-                Health::new(100),
-                Gun::new(2, Bullet::new(5, 20)),
-            ),
+        Self::new_player(id, Self::default_character(position))
+    }
+
+    pub fn new_default_bot(id: u8, position: Pos) -> Self {
+        Self::new_bot(id, Self::default_character(position))
+    }
+
+    fn default_character(position: Pos) -> Character {
+        Character::new(
+            1,
+            position,
+            Health::new(100),
+            Gun::new(2, Bullet::new(5, 20)),
         )
     }
 
@@ -77,7 +78,14 @@ impl Movable for Player {
 
 impl Attack for Player {
     fn attack(&mut self, direction: Direction) -> Option<Bullet> {
-        return self.character.gun.shoot(self.get_position(), direction);
+        let mut bullet = self.character.shoot(direction)?;
+        bullet.owner = Some(self.id);
+        Some(bullet)
+    }
+    fn attack_at(&mut self, target: Pos) -> Option<Bullet> {
+        let mut bullet = self.character.shoot_at(target)?;
+        bullet.owner = Some(self.id);
+        Some(bullet)
     }
     fn reloading(&mut self) {
         self.character.gun.reloading_update();
@@ -99,33 +107,29 @@ impl WithId for Player {
     }
 }
 
+impl WithTeam for Player {
+    fn get_team(&self) -> Option<u8> {
+        self.team
+    }
+}
+
 impl WithCharacter for Player {
     fn character(&self) -> &Character {
         &self.character
     }
+
+    fn character_mut(&mut self) -> &mut Character {
+        &mut self.character
+    }
 }
 
 impl DynPlayer for Player {}
 
-#[derive(Serialize)]
-pub struct PlayerInfo {
-    pub character: CharacterInfo,
-}
-
-impl PlayerInfo {
-    pub fn new(character: &Character) -> Self {
+impl From<&Character> for PlayerInfo {
+    fn from(character: &Character) -> Self {
         Self {
-            character: CharacterInfo::new(character),
+            character: character.into(),
+            team: None,
         }
     }
-
-    pub fn without_pos(&mut self) {
-        self.character.without_pos();
-    }
-}
-
-impl Display for PlayerInfo {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Player Info: {:?}", self.character)
-    }
 }
@@ -0,0 +1 @@
+pub use protocol::result::{GameResult, Rank, Tiebreak};
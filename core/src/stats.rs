@@ -0,0 +1 @@
+pub use protocol::stats::PlayerStats;
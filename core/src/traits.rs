@@ -15,10 +15,12 @@ pub trait Movable {
 
 pub trait WithCharacter {
     fn character(&self) -> &Character;
+    fn character_mut(&mut self) -> &mut Character;
 }
 
 pub trait Attack {
     fn attack(&mut self, direction: Direction) -> Option<Bullet>;
+    fn attack_at(&mut self, target: Pos) -> Option<Bullet>;
     fn reloading(&mut self);
 }
 
@@ -31,6 +33,11 @@ pub trait WithId {
     fn get_id(&self) -> u8;
 }
 
+pub trait WithTeam {
+    /// `None` in free-for-all.
+    fn get_team(&self) -> Option<u8>;
+}
+
 pub trait IntoBytes<const N: usize> {
     fn into_bytes(&self) -> [u8; N];
 }
@@ -0,0 +1,61 @@
+pub use protocol::zone::{Bounds, ZoneInfo};
+
+use super::pos::Pos;
+
+/// Safe zone that starts shrinking at `start_turn` and then shrinks every `shrink_every` turns,
+/// players outside of it get `damage` every turn.
+#[derive(Debug, Clone)]
+pub struct Zone {
+    bounds: Bounds,
+    start_turn: u16,
+    shrink_every: u16,
+    damage: u8,
+}
+
+impl Zone {
+    pub fn new(map_size: u8, start_turn: u16, shrink_every: u16, damage: u8) -> Self {
+        let max = map_size.saturating_sub(1);
+        Self {
+            bounds: Bounds::new((0, 0).into(), (max, max).into()),
+            start_turn,
+            shrink_every: shrink_every.max(1),
+            damage,
+        }
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.bounds.contains(pos)
+    }
+
+    pub fn get_damage(&self) -> u8 {
+        self.damage
+    }
+
+    pub fn update(&mut self, turn: u16) {
+        if turn >= self.start_turn && (turn - self.start_turn).is_multiple_of(self.shrink_every) {
+            self.bounds = self.bounds.shrink();
+        }
+    }
+
+    fn turns_to_shrink(&self, turn: u16) -> u16 {
+        if turn < self.start_turn {
+            self.start_turn - turn
+        } else {
+            self.shrink_every - (turn - self.start_turn) % self.shrink_every
+        }
+    }
+
+    pub fn info(&self, turn: u16) -> ZoneInfo {
+        let next = self.bounds.shrink();
+        ZoneInfo {
+            current: self.bounds,
+            next: if next == self.bounds {
+                None
+            } else {
+                Some(next)
+            },
+            turns_to_shrink: self.turns_to_shrink(turn),
+            damage: self.damage,
+        }
+    }
+}
@@ -0,0 +1,100 @@
+pub mod common;
+
+use common::hero;
+use core::{
+    ability::{DASH_COOLDOWN, DASH_RANGE, MINE_DAMAGE, SHIELD_AMOUNT},
+    direction::Direction,
+    game::LikeGame,
+    map::{Cell, Map},
+    player::Action,
+    pos::Pos,
+};
+
+/// Duel on the 7x7 map with the bushes in the top right corner.
+fn duel(first: Pos, second: Pos) -> Box<dyn LikeGame> {
+    let mut map = Map::new_empty(7);
+    map.set_cell(Pos::new(6, 6), Cell::Bushes);
+    common::duel(map, hero(1, first), second).build()
+}
+
+#[test]
+fn shield_blocks_damage_before_health() {
+    let mut game = duel(Pos::new(0, 0), Pos::new(2, 0));
+    game.update(vec![
+        vec![Action::Nothing; 2],
+        vec![Action::Shield, Action::Nothing],
+    ]);
+    game.update(vec![
+        vec![
+            Action::Attack {
+                direction: Direction::Right,
+            },
+            Action::Nothing,
+        ],
+        vec![Action::Nothing; 2],
+    ]);
+    let target = &game.view(1).unwrap().players[1].character;
+    assert_eq!(target.health, 100);
+    assert_eq!(target.shield, SHIELD_AMOUNT - 20);
+}
+
+#[test]
+fn mine_explodes_under_the_enemy() {
+    let mut game = duel(Pos::new(0, 0), Pos::new(2, 0));
+    game.update(vec![
+        vec![Action::PlaceMine, Action::Nothing],
+        vec![Action::Nothing; 2],
+    ]);
+    assert_eq!(game.view(0).unwrap().mines.len(), 1);
+    assert!(game.view(1).unwrap().mines.is_empty());
+    let left = Action::Move {
+        direction: Direction::Left,
+        range: 1,
+    };
+    game.update(vec![vec![Action::Nothing; 2], vec![left, left]]);
+    let info = game.view(0).unwrap();
+    assert!(info.mines.is_empty());
+    assert_eq!(info.players[1].character.health, 100 - MINE_DAMAGE);
+}
+
+#[test]
+fn reveal_shows_enemies_in_bushes() {
+    let mut game = duel(Pos::new(0, 0), Pos::new(6, 6));
+    game.update(vec![vec![Action::Nothing; 2], vec![Action::Nothing; 2]]);
+    assert!(game.view(0).unwrap().players[1].character.pos.is_none());
+    game.update(vec![
+        vec![Action::Reveal, Action::Nothing],
+        vec![Action::Nothing; 2],
+    ]);
+    assert_eq!(
+        game.view(0).unwrap().players[1].character.pos,
+        Some(Pos::new(6, 6))
+    );
+}
+
+#[test]
+fn dash_ignores_speed_and_waits_for_cooldown() {
+    let mut game = duel(Pos::new(0, 0), Pos::new(0, 6));
+    let dash = Action::Dash {
+        direction: Direction::Right,
+    };
+    game.update(vec![vec![dash, dash], vec![Action::Nothing; 2]]);
+    let me = &game.view(0).unwrap().players[0].character;
+    assert_eq!(me.pos, Some(Pos::new(DASH_RANGE, 0)));
+    assert_eq!(me.cooldowns.dash, DASH_COOLDOWN);
+}
+
+#[test]
+fn blocked_dash_keeps_the_cooldown() {
+    let mut game = duel(Pos::new(0, 0), Pos::new(0, 6));
+    let dashes = [Direction::Left, Direction::Bottom, Direction::None];
+    for direction in dashes {
+        game.update(vec![
+            vec![Action::Dash { direction }, Action::Nothing],
+            vec![Action::Nothing; 2],
+        ]);
+    }
+    let me = &game.view(0).unwrap().players[0].character;
+    assert_eq!(me.pos, Some(Pos::new(0, 0)));
+    assert_eq!(me.cooldowns.dash, 0);
+}
@@ -0,0 +1,104 @@
+pub mod common;
+
+use common::{duel, hero};
+use core::{
+    character::{Bullet, Character, Gun, Health},
+    direction::Direction,
+    game::GameBuilder,
+    map::{Cell, Map},
+    player::{Action, Player},
+    pos::Pos,
+};
+use protocol::geometry::aim;
+
+fn shoot_at(target: Pos) -> Vec<Action> {
+    vec![Action::AttackAt { target }, Action::Nothing]
+}
+
+#[test]
+fn line_mixes_straight_and_diagonal_steps() {
+    assert_eq!(
+        aim(Pos::new(0, 0), Pos::new(3, 1)),
+        vec![Direction::Right, Direction::TopRight, Direction::Right]
+    );
+    assert!(aim(Pos::new(2, 2), Pos::new(2, 2)).is_empty());
+}
+
+#[test]
+fn aimed_shot_hits_off_the_firing_lines() {
+    let mut game = duel(Map::new_empty(5), hero(1, Pos::new(0, 0)), Pos::new(2, 1))
+        .add_free_aim(true)
+        .build();
+    game.update(vec![shoot_at(Pos::new(2, 1)), vec![Action::Nothing; 2]]);
+    let info = game.view(0).unwrap();
+    assert!(info.free_aim);
+    assert_eq!(info.players[1].character.health, 80);
+}
+
+#[test]
+fn aimed_shot_needs_free_aim() {
+    let mut game = duel(Map::new_empty(5), hero(1, Pos::new(0, 0)), Pos::new(2, 1)).build();
+    game.update(vec![shoot_at(Pos::new(2, 1)), vec![Action::Nothing; 2]]);
+    let info = game.view(0).unwrap();
+    assert_eq!(info.players[1].character.health, 100);
+    assert_eq!(info.players[0].character.gun_reloading_await, 0);
+}
+
+#[test]
+fn first_hero_on_the_line_takes_the_bullet() {
+    let mut game = GameBuilder::default()
+        .add_players(vec![
+            Box::new(Player::new_default_player(0, Pos::new(0, 0))),
+            Box::new(Player::new_default_player(1, Pos::new(1, 0))),
+            Box::new(Player::new_default_player(2, Pos::new(3, 0))),
+        ])
+        .add_free_aim(true)
+        .build();
+    game.update(vec![
+        shoot_at(Pos::new(3, 0)),
+        vec![Action::Nothing; 2],
+        vec![Action::Nothing; 2],
+    ]);
+    let info = game.view(0).unwrap();
+    assert_eq!(info.players[1].character.health, 80);
+    assert_eq!(info.players[2].character.health, 100);
+}
+
+#[test]
+fn aimed_shot_is_limited_by_the_range() {
+    let short = Character::new(
+        1,
+        Pos::new(0, 0),
+        Health::new(100),
+        Gun::new(2, Bullet::new(2, 20)),
+    );
+    let map = Map::new_empty(5);
+    let target = Pos::new(3, 0);
+    assert_eq!(
+        map.aimed_shot_path(Pos::new(0, 0), target, 2, &[target]),
+        vec![Pos::new(1, 0), Pos::new(2, 0)]
+    );
+    let mut game = duel(map, short, target).add_free_aim(true).build();
+    game.update(vec![shoot_at(target), vec![Action::Nothing; 2]]);
+    assert_eq!(game.view(0).unwrap().players[1].character.health, 100);
+}
+
+#[test]
+fn wall_on_the_line_stops_the_shot() {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(1, 1), Cell::DestructibleWall { health: 100 });
+    let target = Pos::new(2, 2);
+    assert!(map
+        .aimed_shot_path(Pos::new(0, 0), target, 5, &[target])
+        .is_empty());
+    let mut game = duel(map, hero(1, Pos::new(0, 0)), target)
+        .add_free_aim(true)
+        .build();
+    game.update(vec![shoot_at(target), vec![Action::Nothing; 2]]);
+    let info = game.view(0).unwrap();
+    assert_eq!(info.players[1].character.health, 100);
+    assert_eq!(
+        info.map.get_cell(Pos::new(1, 1)),
+        Cell::DestructibleWall { health: 80 }
+    );
+}
@@ -0,0 +1,118 @@
+use core::{
+    bot::{Chaser, Controller, Difficulty, Kiter, RandomWalker},
+    direction::Direction,
+    game::{GameBuilder, GameInfo, GameState, LikeGame},
+    player::{Action, Player},
+    pos::Pos,
+};
+
+/// What the bot 0 at `bot` sees on the empty 5x5 map with the enemy at `enemy`.
+fn bot_view(bot: Pos, enemy: Pos) -> GameInfo {
+    GameBuilder::default()
+        .add_players(vec![
+            Box::new(Player::new_default_bot(0, bot)),
+            Box::new(Player::new_default_player(1, enemy)),
+        ])
+        .build()
+        .view(0)
+        .unwrap()
+}
+
+fn step(direction: Direction) -> Action {
+    Action::Move {
+        direction,
+        range: 1,
+    }
+}
+
+#[test]
+fn random_walker_steps_and_reloads() {
+    let mut info = bot_view(Pos::new(2, 2), Pos::new(4, 4));
+    info.players[0].character.gun_reloading_await = 1;
+    for _ in 0..50 {
+        let actions = RandomWalker.actions(&info, 2);
+        assert!(matches!(actions[0], Action::Move { range: 1, .. }));
+        assert_eq!(actions[1], Action::Reload);
+    }
+}
+
+#[test]
+fn chaser_shoots_on_the_firing_line_and_comes_closer() {
+    let info = bot_view(Pos::new(0, 0), Pos::new(3, 0));
+    assert_eq!(
+        Chaser.actions(&info, 2),
+        vec![
+            Action::Attack {
+                direction: Direction::Right,
+            },
+            step(Direction::Right),
+        ]
+    );
+    // The longest axis goes first
+    let info = bot_view(Pos::new(0, 0), Pos::new(2, 3));
+    assert_eq!(
+        Chaser.actions(&info, 2),
+        vec![step(Direction::Top), Action::Nothing]
+    );
+}
+
+#[test]
+fn kiter_shoots_and_runs_away() {
+    let info = bot_view(Pos::new(0, 0), Pos::new(4, 0));
+    assert_eq!(
+        Kiter.actions(&info, 2),
+        vec![
+            Action::Attack {
+                direction: Direction::Right,
+            },
+            step(Direction::Top),
+        ]
+    );
+    // Too close and off the firing line
+    let info = bot_view(Pos::new(2, 2), Pos::new(3, 3));
+    assert_eq!(
+        Kiter.actions(&info, 2),
+        vec![step(Direction::Left), Action::Nothing]
+    );
+    // Far away, the shortest way to the firing line
+    let info = bot_view(Pos::new(0, 0), Pos::new(3, 4));
+    assert_eq!(
+        Kiter.actions(&info, 2),
+        vec![step(Direction::Right), Action::Nothing]
+    );
+}
+
+/// The server fills the free places of a match with the bots of its difficulty.
+fn game_with_bot(difficulty: Difficulty) -> Box<dyn LikeGame> {
+    GameBuilder::default()
+        .add_actions_count(2)
+        .add_time_limit(200)
+        .add_bot(
+            Box::new(Player::new_default_bot(1, Pos::new(2, 0))),
+            difficulty.controller(),
+        )
+        .add_players(vec![Box::new(Player::new_default_player(
+            0,
+            Pos::new(1, 0),
+        ))])
+        .build()
+}
+
+#[test]
+fn games_with_bots_finish() {
+    for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+        let mut game = game_with_bot(difficulty);
+        // The bot plays, the idle hero 0 only waits for the end
+        while game.get_turn() < 200 && game.view(0).is_some() {
+            game.update(vec![]);
+        }
+        match (difficulty, game.state()) {
+            (Difficulty::Medium, GameState::End { result }) => assert_eq!(result.winners, vec![1]),
+            (Difficulty::Medium, _) => panic!("the chaser must shoot the idle hero"),
+            (_, state) => assert!(matches!(
+                state,
+                GameState::End { .. } | GameState::TimeIsOver { .. }
+            )),
+        }
+    }
+}
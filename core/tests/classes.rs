@@ -0,0 +1,38 @@
+use core::{
+    character::{Character, ClassStats},
+    game::GameBuilder,
+    player::Player,
+    pos::Pos,
+};
+
+#[test]
+fn class_stats_reach_character_info() {
+    let scout = ClassStats {
+        name: String::from("scout"),
+        speed: 2,
+        health: 70,
+        reload_time: 2,
+        bullet_range: 3,
+        bullet_damage: 15,
+    };
+    let game = GameBuilder::default()
+        .add_players(vec![Box::new(Player::new_player(
+            0,
+            Character::from_class(&scout, Pos::new(1, 0)),
+        ))])
+        .build();
+    let character = &game.view(0).unwrap().players[0].character;
+    assert_eq!(character.class.as_deref(), Some("scout"));
+    assert_eq!(
+        (character.speed, character.health, character.max_health),
+        (2, 70, 70)
+    );
+    assert_eq!(
+        (
+            character.reload_time,
+            character.bullet_range,
+            character.bullet_damage
+        ),
+        (2, 3, 15)
+    );
+}
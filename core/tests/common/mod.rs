@@ -0,0 +1,40 @@
+//! Helpers shared by the game tests.
+
+use core::{
+    character::{Bullet, Character, Gun, Health},
+    direction::Direction,
+    game::GameBuilder,
+    map::Map,
+    player::{Action, Player},
+    pos::Pos,
+};
+
+/// Hero with the gun and the health of the default player.
+pub fn hero(speed: u8, pos: Pos) -> Character {
+    Character::new(
+        speed,
+        pos,
+        Health::new(100),
+        Gun::new(2, Bullet::new(5, 20)),
+    )
+}
+
+/// Game of the player 0 with the `first` hero and the default player 1 at `second`,
+/// the rest of the settings can be added before `build`.
+pub fn duel(map: Map, first: Character, second: Pos) -> GameBuilder {
+    GameBuilder::default().add_map(map).add_players(vec![
+        Box::new(Player::new_player(0, first)),
+        Box::new(Player::new_default_player(1, second)),
+    ])
+}
+
+/// One cell in the `direction`, the second action of the turn does nothing.
+pub fn step(direction: Direction) -> Vec<Action> {
+    vec![
+        Action::Move {
+            direction,
+            range: 1,
+        },
+        Action::Nothing,
+    ]
+}
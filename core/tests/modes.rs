@@ -0,0 +1,147 @@
+pub mod common;
+
+use common::step;
+use core::{
+    character::{Bullet, Character, Gun, Health},
+    direction::Direction,
+    game::{GameBuilder, GameState, LikeGame},
+    mode::{CaptureTheFlag, Deathmatch, KingOfTheHill, ObjectiveInfo},
+    player::{Action, Player},
+    pos::Pos,
+    zone::Bounds,
+};
+
+fn flags_of(game: &dyn LikeGame) -> Vec<(u8, Option<u8>)> {
+    match game.view(0).and_then(|info| info.objective) {
+        Some(ObjectiveInfo::CaptureTheFlag { flags, .. }) => {
+            flags.iter().map(|flag| (flag.team, flag.carrier)).collect()
+        }
+        _ => panic!("the game must have flags"),
+    }
+}
+
+#[test]
+fn flag_is_carried_home_and_captured() {
+    let mut game = GameBuilder::default()
+        .add_players(vec![
+            Box::new(Player::new_default_player(0, Pos::new(0, 0)).with_team(0)),
+            Box::new(Player::new_default_player(1, Pos::new(4, 4)).with_team(1)),
+        ])
+        .add_mode(Box::new(CaptureTheFlag::new(
+            &[(0, Pos::new(0, 0)), (1, Pos::new(1, 0))],
+            1,
+        )))
+        .build();
+    game.update(vec![step(Direction::Right), vec![Action::Nothing; 2]]);
+    assert_eq!(flags_of(game.as_ref()), vec![(0, None), (1, Some(0))]);
+    game.update(vec![step(Direction::Left), vec![Action::Nothing; 2]]);
+    let result = match game.state() {
+        GameState::End { result } => result,
+        _ => panic!("the capture must end the game"),
+    };
+    assert_eq!(result.winners, vec![0]);
+    assert_eq!(result.teams[0].team, 0);
+    assert_eq!(result.teams[0].score, 1);
+}
+
+#[test]
+fn holding_the_hill_earns_points() {
+    let mut game = GameBuilder::default()
+        .add_players(vec![
+            Box::new(Player::new_default_player(0, Pos::new(2, 2))),
+            Box::new(Player::new_default_player(1, Pos::new(4, 4))),
+        ])
+        .add_mode(Box::new(KingOfTheHill::new(
+            Bounds::new(Pos::new(2, 2), Pos::new(2, 2)),
+            3,
+        )))
+        .build();
+    for _ in 0..2 {
+        game.update(vec![]);
+        assert!(matches!(game.state(), GameState::Continue { .. }));
+    }
+    game.update(vec![]);
+    let result = match game.state() {
+        GameState::End { result } => result,
+        _ => panic!("the hill points must end the game"),
+    };
+    assert_eq!(result.winners, vec![0]);
+    assert_eq!(result.ranking[0].score, 3);
+    assert_eq!(result.ranking[1].score, 0);
+}
+
+#[test]
+fn killed_player_respawns_far_from_the_enemy() {
+    let weak = Character::new(
+        1,
+        Pos::new(2, 0),
+        Health::new(20),
+        Gun::new(0, Bullet::new(5, 20)),
+    );
+    let mut game = GameBuilder::default()
+        .add_players(vec![
+            Box::new(Player::new_default_player(0, Pos::new(0, 0))),
+            Box::new(Player::new_player(1, weak)),
+        ])
+        .add_mode(Box::new(Deathmatch::new(
+            vec![Pos::new(0, 4), Pos::new(4, 4)],
+            1,
+            2,
+        )))
+        .build();
+    let shot = vec![
+        Action::Attack {
+            direction: Direction::Right,
+        },
+        Action::Nothing,
+    ];
+    game.update(vec![shot.clone(), vec![Action::Nothing; 2]]);
+    let info = game.view(0).unwrap();
+    match info.objective {
+        Some(ObjectiveInfo::Deathmatch {
+            scores, respawns, ..
+        }) => {
+            assert_eq!((scores[0].kills, scores[1].deaths), (1, 1));
+            assert_eq!((respawns[0].id, respawns[0].turns_left), (1, 1));
+        }
+        _ => panic!("the game must be a deathmatch"),
+    }
+    assert_eq!(info.players[1].character.health, 0);
+    game.update(vec![]);
+    let info = game.view(0).unwrap();
+    assert_eq!(info.players[1].character.health, 20);
+    assert_eq!(info.players[1].character.pos, Some(Pos::new(4, 4)));
+    assert!(matches!(game.state(), GameState::Continue { .. }));
+}
+
+#[test]
+fn deathmatch_ends_at_the_kills_limit() {
+    let weak = Character::new(
+        1,
+        Pos::new(2, 0),
+        Health::new(20),
+        Gun::new(0, Bullet::new(5, 20)),
+    );
+    let mut game = GameBuilder::default()
+        .add_players(vec![
+            Box::new(Player::new_default_player(0, Pos::new(0, 0))),
+            Box::new(Player::new_player(1, weak)),
+        ])
+        .add_mode(Box::new(Deathmatch::new(vec![Pos::new(4, 4)], 3, 1)))
+        .build();
+    game.update(vec![
+        vec![
+            Action::Attack {
+                direction: Direction::Right,
+            },
+            Action::Nothing,
+        ],
+        vec![Action::Nothing; 2],
+    ]);
+    let result = match game.state() {
+        GameState::End { result } => result,
+        _ => panic!("the kill must end the game"),
+    };
+    assert_eq!(result.winners, vec![0]);
+    assert_eq!(result.stats[1].deaths, 1);
+}
@@ -0,0 +1,116 @@
+pub mod common;
+
+use common::{hero, step};
+use core::{
+    direction::{Direction, Movement},
+    game::LikeGame,
+    map::{Cell, Map},
+    player::Action,
+    pos::Pos,
+    traits::IntoBytes,
+};
+
+fn duel(map: Map, first: Pos, second: Pos, movement: Movement) -> Box<dyn LikeGame> {
+    common::duel(map, hero(1, first), second)
+        .add_movement(movement)
+        .build()
+}
+
+#[test]
+fn diagonal_step_moves_both_coordinates() {
+    let mut game = duel(
+        Map::new_empty(5),
+        Pos::new(0, 0),
+        Pos::new(4, 0),
+        Movement::EightWay,
+    );
+    game.update(vec![step(Direction::TopRight), vec![Action::Nothing; 2]]);
+    let info = game.view(0).unwrap();
+    assert_eq!(info.movement, Movement::EightWay);
+    assert_eq!(info.players[0].character.pos, Some(Pos::new(1, 1)));
+}
+
+#[test]
+fn four_way_game_rejects_diagonals() {
+    let mut game = duel(
+        Map::new_empty(5),
+        Pos::new(0, 0),
+        Pos::new(4, 0),
+        Movement::FourWay,
+    );
+    game.update(vec![step(Direction::TopRight), vec![Action::Nothing; 2]]);
+    let info = game.view(0).unwrap();
+    assert_eq!(info.players[0].character.pos, Some(Pos::new(0, 0)));
+}
+
+#[test]
+fn diagonal_step_cannot_cut_a_wall_corner() {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(1, 0), Cell::Wall);
+    let mut game = duel(map, Pos::new(0, 0), Pos::new(4, 0), Movement::EightWay);
+    game.update(vec![step(Direction::TopRight), vec![Action::Nothing; 2]]);
+    let info = game.view(0).unwrap();
+    assert_eq!(info.players[0].character.pos, Some(Pos::new(0, 0)));
+}
+
+#[test]
+fn bullet_flies_diagonally() {
+    let mut game = duel(
+        Map::new_empty(5),
+        Pos::new(0, 0),
+        Pos::new(2, 2),
+        Movement::EightWay,
+    );
+    game.update(vec![
+        vec![
+            Action::Attack {
+                direction: Direction::TopRight,
+            },
+            Action::Nothing,
+        ],
+        vec![Action::Nothing; 2],
+    ]);
+    let info = game.view(0).unwrap();
+    assert_eq!(info.players[1].character.health, 80);
+}
+
+#[test]
+fn bullet_hits_the_destructible_corner() {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(0, 1), Cell::DestructibleWall { health: 100 });
+    let mut game = duel(map, Pos::new(0, 0), Pos::new(2, 2), Movement::EightWay);
+    game.update(vec![
+        vec![
+            Action::Attack {
+                direction: Direction::TopRight,
+            },
+            Action::Nothing,
+        ],
+        vec![Action::Nothing; 2],
+    ]);
+    let info = game.view(0).unwrap();
+    assert_eq!(info.players[1].character.health, 100);
+    assert_eq!(
+        info.map.get_cell(Pos::new(0, 1)),
+        Cell::DestructibleWall { health: 80 }
+    );
+}
+
+#[test]
+fn cardinal_bytes_keep_their_values() {
+    let bytes: Vec<u8> = [
+        Direction::Bottom,
+        Direction::Top,
+        Direction::Left,
+        Direction::Right,
+        Direction::None,
+        Direction::TopLeft,
+        Direction::TopRight,
+        Direction::BottomLeft,
+        Direction::BottomRight,
+    ]
+    .iter()
+    .map(|direction| direction.into_bytes()[0])
+    .collect();
+    assert_eq!(bytes, (0..9).collect::<Vec<u8>>());
+}
@@ -0,0 +1,108 @@
+pub mod common;
+
+use common::{duel, hero, step};
+use core::{
+    direction::Direction,
+    game::LikeGame,
+    map::Map,
+    pickup::{Pickup, PickupKind},
+    player::Action,
+    pos::Pos,
+};
+
+fn respawn_awaiting(game: &dyn LikeGame) -> u8 {
+    game.view(0).unwrap().pickups[0].respawn_awaiting
+}
+
+#[test]
+fn health_pack_heals_and_respawns() {
+    let pack = Pickup::new(Pos::new(0, 1), PickupKind::Health { amount: 30 }, 3);
+    let mut game = duel(Map::new_empty(5), hero(1, Pos::new(0, 0)), Pos::new(2, 0))
+        .add_pickups(vec![pack])
+        .build();
+    game.update(vec![
+        vec![Action::Nothing; 2],
+        vec![
+            Action::Attack {
+                direction: Direction::Left,
+            },
+            Action::Nothing,
+        ],
+    ]);
+    assert_eq!(game.view(0).unwrap().players[0].character.health, 80);
+    game.update(vec![step(Direction::Top), vec![Action::Nothing; 2]]);
+    assert_eq!(game.view(0).unwrap().players[0].character.health, 100);
+    assert_eq!(respawn_awaiting(game.as_ref()), 3);
+    let mut awaiting = Vec::new();
+    for _ in 0..3 {
+        game.update(vec![vec![Action::Nothing; 2]; 2]);
+        awaiting.push(respawn_awaiting(game.as_ref()));
+    }
+    // The hero still stands on the pack and takes it again as soon as it's back
+    assert_eq!(awaiting, vec![2, 1, 3]);
+}
+
+#[test]
+fn speed_boost_expires_after_its_duration() {
+    let boost = Pickup::new(
+        Pos::new(1, 0),
+        PickupKind::Speed {
+            bonus: 1,
+            duration: 2,
+        },
+        100,
+    );
+    let mut game = duel(Map::new_empty(5), hero(1, Pos::new(0, 0)), Pos::new(4, 4))
+        .add_pickups(vec![boost])
+        .build();
+    let run = |direction| {
+        vec![
+            Action::Move {
+                direction,
+                range: 2,
+            },
+            Action::Nothing,
+        ]
+    };
+    game.update(vec![step(Direction::Right), vec![Action::Nothing; 2]]);
+    let me = &game.view(0).unwrap().players[0].character;
+    assert_eq!(me.boosts.len(), 1);
+    assert_eq!(me.boosts[0].turns_left, 2);
+    game.update(vec![run(Direction::Top), vec![Action::Nothing; 2]]);
+    let me = &game.view(0).unwrap().players[0].character;
+    assert_eq!(me.pos, Some(Pos::new(1, 2)));
+    assert_eq!(me.boosts[0].turns_left, 1);
+    game.update(vec![run(Direction::Top), vec![Action::Nothing; 2]]);
+    let me = &game.view(0).unwrap().players[0].character;
+    assert_eq!(me.pos, Some(Pos::new(1, 4)));
+    assert!(me.boosts.is_empty());
+    game.update(vec![run(Direction::Bottom), vec![Action::Nothing; 2]]);
+    let me = &game.view(0).unwrap().players[0].character;
+    assert_eq!(me.pos, Some(Pos::new(1, 3)));
+}
+
+#[test]
+fn damage_boost_adds_to_the_bullet() {
+    let boost = Pickup::new(
+        Pos::new(0, 0),
+        PickupKind::Damage {
+            bonus: 10,
+            duration: 5,
+        },
+        100,
+    );
+    let mut game = duel(Map::new_empty(5), hero(1, Pos::new(0, 0)), Pos::new(2, 0))
+        .add_pickups(vec![boost])
+        .build();
+    game.update(vec![vec![Action::Nothing; 2]; 2]);
+    game.update(vec![
+        vec![
+            Action::Attack {
+                direction: Direction::Right,
+            },
+            Action::Nothing,
+        ],
+        vec![Action::Nothing; 2],
+    ]);
+    assert_eq!(game.view(0).unwrap().players[1].character.health, 70);
+}
@@ -0,0 +1,107 @@
+use core::{
+    game::{GameBuilder, GameState},
+    map::{Cell, Map},
+    pickup::{Pickup, PickupKind},
+    player::{Action, Player},
+    pos::Pos,
+};
+use protocol::{
+    direction::Direction,
+    geometry::DangerMap,
+    info::GameInfo,
+    message::{MatchEnd, Outcome},
+};
+use serde_json::Value;
+
+fn map_with_walls() -> Map {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(2, 2), Cell::Wall);
+    map.set_cell(Pos::new(3, 3), Cell::DestructibleWall { health: 40 });
+    map.set_cell(Pos::new(0, 4), Cell::Bushes);
+    map
+}
+
+#[test]
+fn server_game_info_parses_in_client() {
+    let mut game = GameBuilder::default()
+        .add_map(map_with_walls())
+        .add_players(vec![
+            Box::new(Player::new_default_player(0, Pos::new(1, 0))),
+            Box::new(Player::new_default_player(1, Pos::new(1, 4))),
+        ])
+        .add_pickups(vec![Pickup::new(
+            Pos::new(4, 4),
+            PickupKind::Speed {
+                bonus: 1,
+                duration: 3,
+            },
+            5,
+        )])
+        .add_zone(1, 1, 10)
+        .build();
+    game.update(vec![
+        vec![
+            Action::Attack {
+                direction: Direction::Top,
+            },
+            Action::Nothing,
+        ],
+        vec![Action::Reload, Action::Nothing],
+    ]);
+    let info = match game.state() {
+        GameState::Continue { info } => info,
+        _ => panic!("the game must continue"),
+    };
+    let json = serde_json::to_string(info).unwrap();
+    let parsed: GameInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        serde_json::to_value(&parsed).unwrap(),
+        serde_json::from_str::<Value>(&json).unwrap()
+    );
+}
+
+#[test]
+fn client_finds_its_hero_in_the_view() {
+    let game = GameBuilder::default()
+        .add_players(vec![
+            Box::new(Player::new_default_player(0, Pos::new(1, 0)).with_team(0)),
+            Box::new(Player::new_default_player(1, Pos::new(0, 3)).with_team(0)),
+            Box::new(Player::new_default_player(2, Pos::new(4, 0)).with_team(1)),
+        ])
+        .build();
+    let json = serde_json::to_string(&game.view(1).unwrap()).unwrap();
+    let info: GameInfo = serde_json::from_str(&json).unwrap();
+    assert_eq!(info.me, Some(1));
+    assert_eq!(
+        info.my_player().unwrap().character.pos,
+        Some(Pos::new(0, 3))
+    );
+    let danger = DangerMap::new(&info);
+    // Only the enemy shoots at us, neither we nor the ally do
+    assert_eq!(danger.get(Pos::new(4, 2)), 20);
+    assert_eq!(danger.get(Pos::new(1, 2)), 0);
+    assert_eq!(danger.get(Pos::new(2, 3)), 0);
+}
+
+#[test]
+fn server_match_end_parses_in_client() {
+    let mut game = GameBuilder::default()
+        .add_players(vec![Box::new(Player::new_default_player(
+            0,
+            Pos::new(1, 0),
+        ))])
+        .build();
+    game.update(vec![]);
+    let result = match game.state() {
+        GameState::End { result } => result.clone(),
+        _ => panic!("the game must end without enemies"),
+    };
+    let json = serde_json::to_string(&MatchEnd {
+        outcome: Outcome::Win,
+        result,
+    })
+    .unwrap();
+    let parsed: MatchEnd = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.outcome, Outcome::Win);
+    assert_eq!(parsed.result.winners, vec![0]);
+}
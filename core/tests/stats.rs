@@ -0,0 +1,61 @@
+use core::{
+    direction::Direction,
+    game::{GameBuilder, GameState, LikeGame},
+    player::{Action, Player},
+    pos::Pos,
+    stats::PlayerStats,
+};
+
+fn stats_at_the_end(game: &dyn LikeGame) -> Vec<PlayerStats> {
+    match game.state() {
+        GameState::TimeIsOver { result } | GameState::End { result } => result.stats.clone(),
+        _ => panic!("the game must be over"),
+    }
+}
+
+#[test]
+fn stats_count_actions_and_turns() {
+    let mut game = GameBuilder::default()
+        .add_time_limit(3)
+        .add_players(vec![
+            Box::new(Player::new_default_player(0, Pos::new(0, 0))),
+            Box::new(Player::new_default_player(1, Pos::new(3, 0))),
+        ])
+        .build();
+    let idle = vec![Action::Nothing; 2];
+    game.update(vec![
+        vec![
+            Action::Attack {
+                direction: Direction::Right,
+            },
+            Action::Move {
+                direction: Direction::Top,
+                range: 1,
+            },
+        ],
+        idle.clone(),
+    ]);
+    game.update(vec![vec![Action::Reload, Action::Reload], idle.clone()]);
+    game.update(vec![
+        vec![
+            Action::Move {
+                direction: Direction::Left,
+                range: 1,
+            },
+            Action::Nothing,
+        ],
+        idle,
+    ]);
+    let stats = stats_at_the_end(game.as_ref());
+    let (shooter, target) = (&stats[0], &stats[1]);
+    assert_eq!(shooter.shots_fired, 1);
+    assert_eq!(shooter.hits, 1);
+    assert_eq!(shooter.first_hit_turn, Some(0));
+    assert_eq!(shooter.distance_moved, 1);
+    assert_eq!(shooter.reloading_turns, 1);
+    assert_eq!(shooter.idle_turns, 0);
+    assert_eq!(shooter.invalid_actions, 1);
+    assert_eq!(target.damage_taken, shooter.damage_dealt);
+    assert!(target.damage_taken > 0);
+    assert_eq!(target.idle_turns, 3);
+}
@@ -0,0 +1,90 @@
+pub mod common;
+
+use common::hero;
+use core::{
+    direction::Direction,
+    game::{GameBuilder, GameInfo, LikeGame},
+    map::{Cell, Map},
+    player::{Action, Player},
+    pos::Pos,
+};
+
+/// The player 0 hides in the bushes in the center of the map.
+fn duel(enemy: Pos) -> Box<dyn LikeGame> {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(2, 2), Cell::Bushes);
+    common::duel(map, hero(1, Pos::new(2, 2)), enemy).build()
+}
+
+#[test]
+fn hidden_hero_sees_itself_and_enemies_see_nothing() {
+    let mut game = duel(Pos::new(4, 4));
+    game.update(vec![]);
+    let me = &game.view(0).unwrap().players[0].character;
+    assert_eq!(me.pos, Some(Pos::new(2, 2)));
+    assert!(!me.concealed);
+    let hidden = &game.view(1).unwrap().players[0].character;
+    assert!(hidden.concealed);
+    assert_eq!(hidden.pos, None);
+    assert_eq!((hidden.health, hidden.bullet_damage), (0, 0));
+}
+
+#[test]
+fn adjacent_enemy_sees_into_the_bushes() {
+    let mut game = duel(Pos::new(3, 3));
+    game.update(vec![]);
+    let hidden = &game.view(1).unwrap().players[0].character;
+    assert!(!hidden.concealed);
+    assert_eq!(hidden.pos, Some(Pos::new(2, 2)));
+}
+
+#[test]
+fn shot_exposes_the_shooter_for_one_turn() {
+    let mut game = duel(Pos::new(4, 4));
+    game.update(vec![
+        vec![
+            Action::Attack {
+                direction: Direction::Left,
+            },
+            Action::Nothing,
+        ],
+        vec![Action::Nothing; 2],
+    ]);
+    let shooter = &game.view(1).unwrap().players[0].character;
+    assert_eq!(shooter.pos, Some(Pos::new(2, 2)));
+    game.update(vec![]);
+    assert!(game.view(1).unwrap().players[0].character.concealed);
+}
+
+#[test]
+fn receiver_tells_itself_and_allies_from_hidden_enemies() {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(2, 2), Cell::Bushes);
+    map.set_cell(Pos::new(4, 4), Cell::Bushes);
+    let game = GameBuilder::default()
+        .add_map(map)
+        .add_players(vec![
+            Box::new(Player::new_default_player(0, Pos::new(2, 2)).with_team(0)),
+            Box::new(Player::new_default_player(1, Pos::new(0, 0)).with_team(0)),
+            Box::new(Player::new_default_player(2, Pos::new(4, 4)).with_team(1)),
+        ])
+        .build();
+    let received = |id: u8| -> GameInfo {
+        serde_json::from_str(&serde_json::to_string(&game.view(id).unwrap()).unwrap()).unwrap()
+    };
+    let ally_view = received(1);
+    let me = ally_view.my_player().unwrap();
+    assert_eq!(me.character.pos, Some(Pos::new(0, 0)));
+    let ally = &ally_view.players[0];
+    assert!(ally.is_ally(me));
+    assert_eq!(ally.character.pos, Some(Pos::new(2, 2)));
+    let enemy = &ally_view.players[2];
+    assert!(!enemy.is_ally(me));
+    assert!(enemy.character.concealed);
+    let enemy_view = received(2);
+    assert_eq!(enemy_view.me, Some(2));
+    let me = enemy_view.my_player().unwrap();
+    assert!(!me.character.concealed);
+    assert_eq!(me.character.pos, Some(Pos::new(4, 4)));
+    assert!(enemy_view.players[0].character.concealed);
+}
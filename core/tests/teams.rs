@@ -0,0 +1,86 @@
+use core::{
+    direction::Direction,
+    game::{GameBuilder, GameState},
+    map::{Cell, Map},
+    player::{Action, Player},
+    pos::Pos,
+};
+
+/// Map with the bushes in the top left corner.
+fn bushes() -> Map {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(0, 4), Cell::Bushes);
+    map
+}
+
+#[test]
+fn team_mates_see_each_other_in_bushes() {
+    let mut game = GameBuilder::default()
+        .add_map(bushes())
+        .add_players(vec![
+            Box::new(Player::new_default_player(0, Pos::new(0, 4)).with_team(0)),
+            Box::new(Player::new_default_player(1, Pos::new(1, 0)).with_team(0)),
+            Box::new(Player::new_default_player(2, Pos::new(4, 0)).with_team(1)),
+        ])
+        .build();
+    game.update(vec![]);
+    let ally = game.view(1).unwrap();
+    let enemy = game.view(2).unwrap();
+    assert!(ally.players[0].character.pos.is_some());
+    assert!(enemy.players[0].character.pos.is_none());
+    assert_eq!(enemy.players[2].team, Some(1));
+}
+
+#[test]
+fn last_team_standing_wins_together() {
+    let mut game = GameBuilder::default()
+        .add_players(vec![
+            Box::new(Player::new_default_player(0, Pos::new(1, 0)).with_team(3)),
+            Box::new(Player::new_default_player(1, Pos::new(2, 0)).with_team(3)),
+        ])
+        .build();
+    game.update(vec![]);
+    let result = match game.state() {
+        GameState::End { result } => result.clone(),
+        _ => panic!("the game must end without enemy teams"),
+    };
+    assert_eq!(result.winners, vec![0, 1]);
+    assert_eq!(result.teams.len(), 1);
+    assert_eq!(result.teams[0].team, 3);
+    assert_eq!(result.teams[0].alive, 2);
+}
+
+/// The hero 0 shoots through its ally 1 at the enemy 2, returns the health of the ally
+/// and the enemy.
+fn shot_through_ally(friendly_fire: bool) -> (u8, u8) {
+    let mut game = GameBuilder::default()
+        .add_friendly_fire(friendly_fire)
+        .add_players(vec![
+            Box::new(Player::new_default_player(0, Pos::new(0, 0)).with_team(0)),
+            Box::new(Player::new_default_player(1, Pos::new(2, 0)).with_team(0)),
+            Box::new(Player::new_default_player(2, Pos::new(4, 0)).with_team(1)),
+        ])
+        .build();
+    game.update(vec![
+        vec![
+            Action::Attack {
+                direction: Direction::Right,
+            },
+            Action::Nothing,
+        ],
+        vec![Action::Nothing; 2],
+        vec![Action::Nothing; 2],
+    ]);
+    let players = game.view(0).unwrap().players;
+    (players[1].character.health, players[2].character.health)
+}
+
+#[test]
+fn bullets_pass_through_allies_without_friendly_fire() {
+    assert_eq!(shot_through_ally(false), (100, 80));
+}
+
+#[test]
+fn bullets_hit_allies_with_friendly_fire() {
+    assert_eq!(shot_through_ally(true), (80, 100));
+}
@@ -0,0 +1,97 @@
+pub mod common;
+
+use common::{duel, hero};
+use core::{
+    direction::Direction,
+    game::LikeGame,
+    map::{Cell, Map},
+    player::Action,
+    pos::Pos,
+};
+
+fn walk(direction: Direction, range: u8) -> Vec<Action> {
+    vec![Action::Move { direction, range }, Action::Nothing]
+}
+
+fn my_pos(game: &dyn LikeGame) -> Option<Pos> {
+    game.view(0).unwrap().players[0].character.pos
+}
+
+#[test]
+fn water_stops_heroes_but_not_bullets() {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(1, 0), Cell::Water);
+    let mut game = duel(map, hero(1, Pos::new(0, 0)), Pos::new(3, 0)).build();
+    game.update(vec![walk(Direction::Right, 1), vec![Action::Nothing; 2]]);
+    assert_eq!(my_pos(game.as_ref()), Some(Pos::new(0, 0)));
+    game.update(vec![
+        vec![
+            Action::Attack {
+                direction: Direction::Right,
+            },
+            Action::Nothing,
+        ],
+        vec![Action::Nothing; 2],
+    ]);
+    assert_eq!(game.view(0).unwrap().players[1].character.health, 80);
+}
+
+#[test]
+fn mud_lets_a_slow_hero_move_every_other_turn() {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(1, 0), Cell::Mud);
+    map.set_cell(Pos::new(2, 0), Cell::Mud);
+    let mut game = duel(map, hero(1, Pos::new(0, 0)), Pos::new(4, 4)).build();
+    let mut path = Vec::new();
+    for _ in 0..5 {
+        let step = Action::Move {
+            direction: Direction::Right,
+            range: 1,
+        };
+        game.update(vec![vec![step; 2], vec![Action::Nothing; 2]]);
+        path.push(my_pos(game.as_ref()).unwrap().x);
+    }
+    assert_eq!(path, vec![1, 1, 2, 2, 4]);
+}
+
+#[test]
+fn mud_stops_a_fast_hero() {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(1, 0), Cell::Mud);
+    let mut game = duel(map, hero(3, Pos::new(0, 0)), Pos::new(4, 4)).build();
+    game.update(vec![walk(Direction::Right, 3), vec![Action::Nothing; 2]]);
+    assert_eq!(my_pos(game.as_ref()), Some(Pos::new(1, 0)));
+    assert_eq!(game.view(0).unwrap().players[0].character.mud_turns, 2);
+}
+
+#[test]
+fn teleport_takes_the_hero_to_its_pair() {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(1, 0), Cell::Teleport { channel: 7 });
+    map.set_cell(Pos::new(3, 3), Cell::Teleport { channel: 7 });
+    let mut game = duel(map, hero(3, Pos::new(0, 0)), Pos::new(4, 0)).build();
+    game.update(vec![walk(Direction::Right, 3), vec![Action::Nothing; 2]]);
+    assert_eq!(my_pos(game.as_ref()), Some(Pos::new(3, 3)));
+}
+
+#[test]
+fn conveyor_is_one_way_and_carries_heroes() {
+    let mut map = Map::new_empty(5);
+    map.set_cell(
+        Pos::new(1, 0),
+        Cell::Conveyor {
+            direction: Direction::Left,
+        },
+    );
+    map.set_cell(
+        Pos::new(2, 1),
+        Cell::Conveyor {
+            direction: Direction::Top,
+        },
+    );
+    let mut game = duel(map, hero(1, Pos::new(0, 0)), Pos::new(2, 0)).build();
+    game.update(vec![walk(Direction::Right, 1), walk(Direction::Top, 1)]);
+    let info = game.view(0).unwrap();
+    assert_eq!(info.players[0].character.pos, Some(Pos::new(0, 0)));
+    assert_eq!(info.players[1].character.pos, Some(Pos::new(2, 2)));
+}
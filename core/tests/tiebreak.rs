@@ -0,0 +1,102 @@
+use core::{
+    character::{Bullet, Character, Gun, Health},
+    direction::Direction,
+    game::{GameBuilder, GameState},
+    player::{Action, Player},
+    pos::Pos,
+    result::{GameResult, Tiebreak},
+};
+
+fn character(pos: Pos, health: u8, damage: u8) -> Character {
+    Character::new(
+        1,
+        pos,
+        Health::new(health),
+        Gun::new(2, Bullet::new(5, damage)),
+    )
+}
+
+fn attack(direction: Direction) -> Vec<Action> {
+    vec![Action::Attack { direction }, Action::Nothing]
+}
+
+/// One turn before the time is over: the hero 0 kills the hero 3, the heroes 1 and 2
+/// shoot each other. The heroes 0, 1, 2 end with 90, 40, 50 health, 20, 50, 60 damage
+/// dealt and 1, 0, 0 kills.
+fn result_at_the_time_limit(tiebreak: Tiebreak) -> GameResult {
+    let mut game = GameBuilder::default()
+        .add_time_limit(1)
+        .add_tiebreak(tiebreak)
+        .add_players(vec![
+            Box::new(Player::new_player(0, character(Pos::new(0, 0), 90, 20))),
+            Box::new(Player::new_player(1, character(Pos::new(2, 0), 100, 50))),
+            Box::new(Player::new_player(2, character(Pos::new(4, 0), 100, 60))),
+            Box::new(Player::new_player(3, character(Pos::new(0, 2), 20, 20))),
+        ])
+        .build();
+    game.update(vec![
+        attack(Direction::Top),
+        attack(Direction::Right),
+        attack(Direction::Left),
+    ]);
+    match game.state() {
+        GameState::TimeIsOver { result } => result.clone(),
+        _ => panic!("the time must be over"),
+    }
+}
+
+/// `(id, place, score)` of every hero by id.
+fn places(result: &GameResult) -> Vec<(u8, u8, u16)> {
+    let mut places: Vec<_> = result
+        .ranking
+        .iter()
+        .map(|rank| (rank.id, rank.place, rank.score))
+        .collect();
+    places.sort();
+    places
+}
+
+#[test]
+fn time_over_ranks_by_health() {
+    let result = result_at_the_time_limit(Tiebreak::Health);
+    assert_eq!(result.winners, vec![0]);
+    assert!(result.draw.is_empty());
+    assert_eq!(
+        places(&result),
+        vec![(0, 1, 90), (1, 3, 40), (2, 2, 50), (3, 4, 0)]
+    );
+}
+
+#[test]
+fn time_over_ranks_by_damage_dealt() {
+    let result = result_at_the_time_limit(Tiebreak::DamageDealt);
+    assert_eq!(result.winners, vec![2]);
+    assert!(result.draw.is_empty());
+    assert_eq!(
+        places(&result),
+        vec![(0, 3, 20), (1, 2, 50), (2, 1, 60), (3, 4, 0)]
+    );
+}
+
+#[test]
+fn time_over_ranks_by_kills() {
+    let result = result_at_the_time_limit(Tiebreak::Kills);
+    assert_eq!(result.winners, vec![0]);
+    assert!(result.draw.is_empty());
+    // The heroes without kills share the second place
+    assert_eq!(
+        places(&result),
+        vec![(0, 1, 1), (1, 2, 0), (2, 2, 0), (3, 4, 0)]
+    );
+}
+
+#[test]
+fn time_over_is_a_draw_of_the_living() {
+    let result = result_at_the_time_limit(Tiebreak::Draw);
+    assert!(result.winners.is_empty());
+    assert_eq!(result.draw, vec![0, 1, 2]);
+    assert_eq!(
+        places(&result),
+        vec![(0, 1, 0), (1, 1, 0), (2, 1, 0), (3, 4, 0)]
+    );
+}
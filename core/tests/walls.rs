@@ -0,0 +1,56 @@
+pub mod common;
+
+use common::{duel, hero};
+use core::{
+    direction::Direction,
+    game::LikeGame,
+    map::{Cell, Map},
+    player::Action,
+    pos::Pos,
+};
+
+const WALL: Pos = Pos::new(2, 0);
+
+/// Shoots to the right as soon as the gun is loaded, the enemy does nothing.
+fn shoot(game: &mut dyn LikeGame) {
+    while game.view(0).unwrap().players[0]
+        .character
+        .gun_reloading_await
+        > 0
+    {
+        game.update(vec![
+            vec![Action::Reload, Action::Nothing],
+            vec![Action::Nothing; 2],
+        ]);
+    }
+    game.update(vec![
+        vec![
+            Action::Attack {
+                direction: Direction::Right,
+            },
+            Action::Nothing,
+        ],
+        vec![Action::Nothing; 2],
+    ]);
+}
+
+#[test]
+fn shots_break_a_destructible_wall() {
+    let mut map = Map::new_empty(5);
+    map.set_cell(WALL, Cell::DestructibleWall { health: 30 });
+    let mut game = duel(map, hero(1, Pos::new(0, 0)), Pos::new(4, 0)).build();
+    shoot(game.as_mut());
+    let info = game.view(0).unwrap();
+    assert_eq!(
+        info.map.get_cell(WALL),
+        Cell::DestructibleWall { health: 10 }
+    );
+    assert_eq!(info.players[1].character.health, 100);
+    shoot(game.as_mut());
+    let info = game.view(0).unwrap();
+    assert_eq!(info.map.get_cell(WALL), Cell::Empty);
+    assert_eq!(info.players[1].character.health, 100);
+    // Nothing stops the next bullet anymore
+    shoot(game.as_mut());
+    assert_eq!(game.view(0).unwrap().players[1].character.health, 80);
+}
@@ -0,0 +1,52 @@
+pub mod common;
+
+use common::{duel, hero};
+use core::{
+    map::Map,
+    player::Action,
+    pos::Pos,
+    zone::{Bounds, Zone},
+};
+
+fn bounds(min: (u8, u8), max: (u8, u8)) -> Bounds {
+    Bounds::new(min.into(), max.into())
+}
+
+#[test]
+fn zone_shrinks_on_schedule() {
+    let mut zone = Zone::new(5, 2, 3, 10);
+    let info = zone.info(0);
+    assert_eq!(info.current, bounds((0, 0), (4, 4)));
+    assert_eq!(info.next, Some(bounds((1, 1), (3, 3))));
+    assert_eq!(info.turns_to_shrink, 2);
+    zone.update(1);
+    assert_eq!(zone.info(1).current, bounds((0, 0), (4, 4)));
+    assert_eq!(zone.info(1).turns_to_shrink, 1);
+    zone.update(2);
+    assert_eq!(zone.info(2).current, bounds((1, 1), (3, 3)));
+    assert_eq!(zone.info(2).turns_to_shrink, 3);
+    for turn in 3..=5 {
+        zone.update(turn);
+    }
+    let info = zone.info(5);
+    assert_eq!(info.current, bounds((2, 2), (2, 2)));
+    assert_eq!(info.next, None);
+}
+
+#[test]
+fn zone_of_an_empty_map_does_not_underflow() {
+    let zone = Zone::new(0, 0, 1, 10);
+    assert_eq!(zone.info(0).current, bounds((0, 0), (0, 0)));
+}
+
+#[test]
+fn heroes_outside_the_zone_take_damage() {
+    let mut game = duel(Map::new_empty(5), hero(1, Pos::new(0, 0)), Pos::new(2, 2))
+        .add_zone(1, 1, 10)
+        .build();
+    game.update(vec![vec![Action::Nothing; 2]; 2]);
+    let info = game.view(0).unwrap();
+    assert_eq!(info.zone.unwrap().current, bounds((1, 1), (3, 3)));
+    assert_eq!(info.players[0].character.health, 90);
+    assert_eq!(info.players[1].character.health, 100);
+}
@@ -0,0 +1,104 @@
+//! Async client runtime on tokio, enabled by the `async` feature. Works like the blocking
+//! [`runtime`](super::runtime), but `on_turn` is async, so a bot can think with
+//! `tokio::time::timeout` and many bots can play in one process:
+//!
+//! ```no_run
+//! # async fn bots<B: game_client_api::async_runtime::AsyncBot + 'static>(bots: Vec<B>) {
+//! for mut bot in bots {
+//!     tokio::spawn(async move {
+//!         game_client_api::async_runtime::run_async("ws://localhost:8080", &mut bot).await
+//!     });
+//! }
+//! # }
+//! ```
+
+use std::{future::Future, time::Duration};
+
+use futures_util::{SinkExt, StreamExt};
+use protocol::message::{ACCEPT_CONNECTION, SERVER_SHUTDOWN};
+use tokio::{net::TcpStream, time::sleep};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use url::Url;
+
+use super::{runtime::ClientError, Action, GameInfo, MatchEnd, Response};
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+pub trait AsyncBot: Send {
+    /// Called with the first game state of every match, before `on_turn`.
+    fn on_match_start(&mut self, _info: &GameInfo) {}
+
+    fn on_turn(&mut self, info: &GameInfo) -> impl Future<Output = Vec<Action>> + Send;
+
+    fn on_match_end(&mut self, _end: &MatchEnd) {}
+
+    /// Called when the match is broken by a transport error, the runtime reconnects after it.
+    fn on_error(&mut self, error: &ClientError) {
+        eprintln!("{}", error);
+    }
+}
+
+/// Plays matches on the server forever, reconnects after the transport errors and returns
+/// the other ones.
+pub async fn run_async<B: AsyncBot>(url: &str, bot: &mut B) -> Result<(), ClientError> {
+    let url = Url::parse(url)?;
+    loop {
+        match play_match_async(&url, bot).await {
+            Ok(_) => {}
+            Err(err) if err.is_transport() => {
+                bot.on_error(&err);
+                sleep(RECONNECT_DELAY).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Connects to the server and plays one match.
+pub async fn play_match_async<B: AsyncBot>(
+    url: &Url,
+    bot: &mut B,
+) -> Result<MatchEnd, ClientError> {
+    let (mut socket, _) = connect_async(url).await?;
+    match read_text(&mut socket).await? {
+        text if text == ACCEPT_CONNECTION => {}
+        text => return Err(ClientError::Handshake(text)),
+    }
+    let mut started = false;
+    loop {
+        let text = read_text(&mut socket).await?;
+        if text == SERVER_SHUTDOWN {
+            return Err(ClientError::Shutdown);
+        }
+        if let Ok(end) = serde_json::from_str::<MatchEnd>(&text) {
+            bot.on_match_end(&end);
+            let _ = socket.close(None).await;
+            return Ok(end);
+        }
+        // Unknown messages are skipped, the server only waits for actions after a game state
+        let info = match serde_json::from_str::<GameInfo>(&text) {
+            Ok(info) => info,
+            Err(_) => continue,
+        };
+        if !started {
+            started = true;
+            bot.on_match_start(&info);
+        }
+        let actions = bot.on_turn(&info).await;
+        let response = serde_json::to_string(&Response::new(&actions))?;
+        socket.send(Message::Text(response)).await?;
+    }
+}
+
+async fn read_text(
+    socket: &mut WebSocketStream<MaybeTlsStream<TcpStream>>,
+) -> Result<String, ClientError> {
+    while let Some(message) = socket.next().await {
+        match message? {
+            Message::Text(text) => return Ok(text),
+            Message::Close(_) => return Err(ClientError::ConnectionClosed),
+            _ => continue,
+        }
+    }
+    Err(ClientError::ConnectionClosed)
+}
@@ -0,0 +1,156 @@
+//! Blocking client runtime: implement [`Bot`] and give it to [`run`], the runtime connects
+//! to the server, plays the matches one by one and reconnects when the connection is lost.
+//! Errors that a reconnect can't fix, like a rejected handshake, are returned to you.
+
+use std::{fmt::Display, net::TcpStream, thread::sleep, time::Duration};
+
+use protocol::message::{ACCEPT_CONNECTION, SERVER_SHUTDOWN};
+use tungstenite::{connect, stream::MaybeTlsStream, Message, WebSocket};
+use url::Url;
+
+use super::{Action, GameInfo, MatchEnd, Response};
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+pub trait Bot {
+    /// Called with the first game state of every match, before `on_turn`.
+    fn on_match_start(&mut self, _info: &GameInfo) {}
+
+    fn on_turn(&mut self, info: &GameInfo) -> Vec<Action>;
+
+    fn on_match_end(&mut self, _end: &MatchEnd) {}
+
+    /// Called when the match is broken by a transport error, the runtime reconnects after it.
+    fn on_error(&mut self, error: &ClientError) {
+        eprintln!("{}", error);
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    Url(url::ParseError),
+    WebSocket(Box<tungstenite::Error>),
+    Json(serde_json::Error),
+    /// The server answered with something else instead of accepting the connection.
+    Handshake(String),
+    ConnectionClosed,
+    /// The server is stopping, the runtime keeps trying to reconnect.
+    Shutdown,
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Url(err) => write!(f, "Invalid server url: {}", err),
+            ClientError::WebSocket(err) => write!(f, "WebSocket error: {}", err),
+            ClientError::Json(err) => write!(f, "JSON error: {}", err),
+            ClientError::Handshake(text) => write!(f, "Unexpected handshake: {}", text),
+            ClientError::ConnectionClosed => write!(f, "Connection closed by the server"),
+            ClientError::Shutdown => write!(f, "The server is shutting down"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl ClientError {
+    /// The connection was lost or the server is stopping, another attempt can succeed.
+    /// Other errors repeat on every attempt: a wrong url, a rejected handshake, a message
+    /// that breaks the protocol.
+    pub fn is_transport(&self) -> bool {
+        match self {
+            ClientError::ConnectionClosed | ClientError::Shutdown => true,
+            ClientError::WebSocket(err) => matches!(
+                **err,
+                tungstenite::Error::Io(_)
+                    | tungstenite::Error::Tls(_)
+                    | tungstenite::Error::Protocol(
+                        tungstenite::error::ProtocolError::ResetWithoutClosingHandshake
+                    )
+            ),
+            ClientError::Url(_) | ClientError::Json(_) | ClientError::Handshake(_) => false,
+        }
+    }
+}
+
+impl From<url::ParseError> for ClientError {
+    fn from(err: url::ParseError) -> Self {
+        ClientError::Url(err)
+    }
+}
+
+impl From<tungstenite::Error> for ClientError {
+    fn from(err: tungstenite::Error) -> Self {
+        match err {
+            tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed => {
+                ClientError::ConnectionClosed
+            }
+            err => ClientError::WebSocket(Box::new(err)),
+        }
+    }
+}
+
+impl From<serde_json::Error> for ClientError {
+    fn from(err: serde_json::Error) -> Self {
+        ClientError::Json(err)
+    }
+}
+
+/// Plays matches on the server forever, reconnects after the transport errors and returns
+/// the other ones.
+pub fn run<B: Bot>(url: &str, bot: &mut B) -> Result<(), ClientError> {
+    let url = Url::parse(url)?;
+    loop {
+        match play_match(&url, bot) {
+            Ok(_) => {}
+            Err(err) if err.is_transport() => {
+                bot.on_error(&err);
+                sleep(RECONNECT_DELAY);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Connects to the server and plays one match.
+pub fn play_match<B: Bot>(url: &Url, bot: &mut B) -> Result<MatchEnd, ClientError> {
+    let (mut socket, _) = connect(url)?;
+    match read_text(&mut socket)? {
+        text if text == ACCEPT_CONNECTION => {}
+        text => return Err(ClientError::Handshake(text)),
+    }
+    let mut started = false;
+    loop {
+        let text = read_text(&mut socket)?;
+        if text == SERVER_SHUTDOWN {
+            return Err(ClientError::Shutdown);
+        }
+        if let Ok(end) = serde_json::from_str::<MatchEnd>(&text) {
+            bot.on_match_end(&end);
+            let _ = socket.close(None);
+            return Ok(end);
+        }
+        // Unknown messages are skipped, the server only waits for actions after a game state
+        let info = match serde_json::from_str::<GameInfo>(&text) {
+            Ok(info) => info,
+            Err(_) => continue,
+        };
+        if !started {
+            started = true;
+            bot.on_match_start(&info);
+        }
+        let actions = bot.on_turn(&info);
+        let response = serde_json::to_string(&Response::new(&actions))?;
+        socket.write_message(Message::Text(response))?;
+    }
+}
+
+fn read_text(socket: &mut WebSocket<MaybeTlsStream<TcpStream>>) -> Result<String, ClientError> {
+    loop {
+        match socket.read_message()? {
+            Message::Text(text) => return Ok(text),
+            Message::Close(_) => return Err(ClientError::ConnectionClosed),
+            _ => continue,
+        }
+    }
+}
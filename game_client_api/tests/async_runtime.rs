@@ -0,0 +1,41 @@
+#![cfg(feature = "async")]
+
+pub mod common;
+
+use common::{lost, one_turn, serve, unknown_class, Recorder};
+use game_client_api::{
+    async_runtime::{run_async, AsyncBot},
+    runtime::{Bot, ClientError},
+    Action, GameInfo, MatchEnd,
+};
+
+impl AsyncBot for Recorder {
+    fn on_match_start(&mut self, info: &GameInfo) {
+        Bot::on_match_start(self, info);
+    }
+
+    async fn on_turn(&mut self, info: &GameInfo) -> Vec<Action> {
+        Bot::on_turn(self, info)
+    }
+
+    fn on_match_end(&mut self, end: &MatchEnd) {
+        Bot::on_match_end(self, end);
+    }
+
+    fn on_error(&mut self, error: &ClientError) {
+        Bot::on_error(self, error);
+    }
+}
+
+#[tokio::test]
+async fn plays_a_match_and_returns_the_rejected_handshake() {
+    let (url, server) = serve(vec![lost, one_turn, unknown_class]);
+    let mut bot = Recorder::default();
+    assert!(matches!(
+        run_async(&url, &mut bot).await,
+        Err(ClientError::Handshake(_))
+    ));
+    assert_eq!(bot.errors.len(), 1);
+    assert_eq!((bot.starts, bot.turns, bot.ends.len()), (1, 1, 1));
+    server.join().unwrap();
+}
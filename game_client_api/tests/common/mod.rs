@@ -0,0 +1,97 @@
+use std::{
+    net::{TcpListener, TcpStream},
+    thread::{self, JoinHandle},
+};
+
+use game_client_api::{
+    runtime::{Bot, ClientError},
+    Action, Direction, GameInfo, MatchEnd,
+};
+use protocol::message::{ACCEPT_CONNECTION, UNKNOWN_CLASS};
+use tungstenite::{accept, Message, WebSocket};
+
+pub const GAME_INFO: &str = include_str!("../../../json_example/game_info.json");
+pub const MATCH_END: &str = include_str!("../../../json_example/match_end.json");
+
+/// What the server does with a connection, returns the texts that the client sent.
+pub type Connection = fn(WebSocket<TcpStream>) -> Vec<String>;
+
+/// Starts a server on a free port that handles its connections by `connections` in order
+/// and then stops, returns its url and the texts of every connection.
+pub fn serve(connections: Vec<Connection>) -> (String, JoinHandle<Vec<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let server = thread::spawn(move || {
+        connections
+            .into_iter()
+            .map(|connection| {
+                let (stream, _) = listener.accept().unwrap();
+                connection(accept(stream).unwrap())
+            })
+            .collect()
+    });
+    (url, server)
+}
+
+/// Accepts the client, sends one game state, waits for the actions and ends the match.
+pub fn one_turn(mut socket: WebSocket<TcpStream>) -> Vec<String> {
+    socket
+        .write_message(Message::Text(String::from(ACCEPT_CONNECTION)))
+        .unwrap();
+    socket
+        .write_message(Message::Text(String::from(GAME_INFO)))
+        .unwrap();
+    let answer = socket.read_message().unwrap().into_text().unwrap();
+    socket
+        .write_message(Message::Text(String::from(MATCH_END)))
+        .unwrap();
+    // The client closes the connection after the end of the match
+    while socket.read_message().is_ok() {}
+    vec![answer]
+}
+
+pub fn unknown_class(mut socket: WebSocket<TcpStream>) -> Vec<String> {
+    socket
+        .write_message(Message::Text(String::from(UNKNOWN_CLASS)))
+        .unwrap();
+    let _ = socket.close(None);
+    let _ = socket.write_pending();
+    Vec::new()
+}
+
+/// Drops the connection without the closing handshake, like a crashed server.
+pub fn lost(socket: WebSocket<TcpStream>) -> Vec<String> {
+    drop(socket);
+    Vec::new()
+}
+
+/// Steps to the right every turn and remembers what the runtime told it.
+#[derive(Default)]
+pub struct Recorder {
+    pub starts: usize,
+    pub turns: usize,
+    pub ends: Vec<MatchEnd>,
+    pub errors: Vec<String>,
+}
+
+impl Bot for Recorder {
+    fn on_match_start(&mut self, _info: &GameInfo) {
+        self.starts += 1;
+    }
+
+    fn on_turn(&mut self, _info: &GameInfo) -> Vec<Action> {
+        self.turns += 1;
+        vec![Action::Move {
+            direction: Direction::Right,
+            range: 1,
+        }]
+    }
+
+    fn on_match_end(&mut self, end: &MatchEnd) {
+        self.ends.push(end.clone());
+    }
+
+    fn on_error(&mut self, error: &ClientError) {
+        self.errors.push(error.to_string());
+    }
+}
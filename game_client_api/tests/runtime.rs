@@ -0,0 +1,52 @@
+pub mod common;
+
+use common::{lost, one_turn, serve, unknown_class, Recorder};
+use game_client_api::{
+    runtime::{play_match, run, ClientError},
+    Action, Direction, Outcome, Response,
+};
+use protocol::message::UNKNOWN_CLASS;
+use url::Url;
+
+#[test]
+fn plays_one_turn_and_the_match_end() {
+    let (url, server) = serve(vec![one_turn]);
+    let mut bot = Recorder::default();
+    let end = play_match(&Url::parse(&url).unwrap(), &mut bot).unwrap();
+    assert_eq!(end.outcome, Outcome::Win);
+    assert_eq!((bot.starts, bot.turns, bot.ends.len()), (1, 1, 1));
+    let texts = server.join().unwrap();
+    let response: Response = serde_json::from_str(&texts[0][0]).unwrap();
+    assert_eq!(
+        response.actions,
+        vec![Action::Move {
+            direction: Direction::Right,
+            range: 1,
+        }]
+    );
+}
+
+#[test]
+fn rejected_handshake_is_returned() {
+    let (url, server) = serve(vec![unknown_class]);
+    let mut bot = Recorder::default();
+    match run(&url, &mut bot) {
+        Err(ClientError::Handshake(text)) => assert_eq!(text, UNKNOWN_CLASS),
+        result => panic!("the handshake must fail, got {:?}", result),
+    }
+    assert!(bot.errors.is_empty());
+    server.join().unwrap();
+}
+
+#[test]
+fn reconnects_after_a_lost_connection() {
+    let (url, server) = serve(vec![lost, one_turn, unknown_class]);
+    let mut bot = Recorder::default();
+    assert!(matches!(
+        run(&url, &mut bot),
+        Err(ClientError::Handshake(_))
+    ));
+    assert_eq!(bot.errors.len(), 1);
+    assert_eq!((bot.turns, bot.ends.len()), (1, 1));
+    server.join().unwrap();
+}
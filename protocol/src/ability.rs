@@ -0,0 +1,35 @@
+//! Active abilities, every hero has all of them, after the use an ability waits for its cooldown.
+
+use serde::{Deserialize, Serialize};
+
+use super::pos::Pos;
+
+/// Cells of the dash, the speed of the hero doesn't matter.
+pub const DASH_RANGE: u8 = 3;
+pub const DASH_COOLDOWN: u8 = 5;
+/// Damage that the shield blocks before it breaks.
+pub const SHIELD_AMOUNT: u8 = 40;
+pub const SHIELD_COOLDOWN: u8 = 8;
+pub const MINE_DAMAGE: u8 = 40;
+pub const MINE_COOLDOWN: u8 = 6;
+/// Turns while the hero and the allies see the enemies in the bushes.
+pub const REVEAL_TURNS: u8 = 3;
+pub const REVEAL_COOLDOWN: u8 = 10;
+
+/// Turns until the ability is ready again, zero means ready.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cooldowns {
+    pub dash: u8,
+    pub shield: u8,
+    pub mine: u8,
+    pub reveal: u8,
+}
+
+/// Mine that explodes under the first enemy that ends a turn on its cell,
+/// only the owner and the allies of the owner see it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Mine {
+    pub pos: Pos,
+    pub owner: u8,
+    pub damage: u8,
+}
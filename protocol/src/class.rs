@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// Stats of a character class, the client picks its class by the name in the handshake.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassStats {
+    pub name: String,
+    /// Cells per move action.
+    pub speed: u8,
+    pub health: u8,
+    /// Turns between shots.
+    pub reload_time: u8,
+    pub bullet_range: u8,
+    pub bullet_damage: u8,
+}
@@ -0,0 +1,117 @@
+use std::fmt::Display;
+
+use ez_colorize::ColorizeDisplay;
+use serde::{Deserialize, Serialize};
+
+/// `Top` increases `y`, `Right` increases `x`, the diagonals are played only with
+/// `Movement::EightWay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Direction {
+    Top,
+    Bottom,
+    Right,
+    Left,
+    None,
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+}
+
+/// Directions that the players can use in the game.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Movement {
+    /// Only `Top`, `Bottom`, `Right` and `Left`.
+    #[default]
+    FourWay,
+    /// The diagonals too, a diagonal step can't cut the corner of an impassable cell.
+    EightWay,
+}
+
+const FOUR_WAY: [Direction; 4] = [
+    Direction::Top,
+    Direction::Bottom,
+    Direction::Right,
+    Direction::Left,
+];
+
+const EIGHT_WAY: [Direction; 8] = [
+    Direction::Top,
+    Direction::Bottom,
+    Direction::Right,
+    Direction::Left,
+    Direction::TopRight,
+    Direction::TopLeft,
+    Direction::BottomRight,
+    Direction::BottomLeft,
+];
+
+impl Movement {
+    /// Directions of the moves and the shots, `Direction::None` isn't in them.
+    pub const fn directions(&self) -> &'static [Direction] {
+        match self {
+            Movement::FourWay => &FOUR_WAY,
+            Movement::EightWay => &EIGHT_WAY,
+        }
+    }
+
+    pub const fn allows(&self, direction: Direction) -> bool {
+        match self {
+            Movement::FourWay => !direction.is_diagonal(),
+            Movement::EightWay => true,
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::Bottom => write!(f, "{}", "Bot".green()),
+            Direction::Top => write!(f, "{}", "Top".green()),
+            Direction::Left => write!(f, "{}", "Left".green()),
+            Direction::Right => write!(f, "{}", "Right".green()),
+            Direction::TopRight => write!(f, "{}", "TopRight".green()),
+            Direction::TopLeft => write!(f, "{}", "TopLeft".green()),
+            Direction::BottomRight => write!(f, "{}", "BotRight".green()),
+            Direction::BottomLeft => write!(f, "{}", "BotLeft".green()),
+            Direction::None => write!(f, "{}", "None".red()),
+        }
+    }
+}
+
+impl Direction {
+    pub const fn opposite(&self) -> Self {
+        match self {
+            Direction::Top => Direction::Bottom,
+            Direction::Bottom => Direction::Top,
+            Direction::Right => Direction::Left,
+            Direction::Left => Direction::Right,
+            Direction::TopRight => Direction::BottomLeft,
+            Direction::TopLeft => Direction::BottomRight,
+            Direction::BottomRight => Direction::TopLeft,
+            Direction::BottomLeft => Direction::TopRight,
+            Direction::None => Direction::None,
+        }
+    }
+
+    pub const fn is_diagonal(&self) -> bool {
+        self.components().is_some()
+    }
+
+    /// Vertical and horizontal parts of a diagonal, `None` for the other directions.
+    pub const fn components(&self) -> Option<(Direction, Direction)> {
+        match self {
+            Direction::TopRight => Some((Direction::Top, Direction::Right)),
+            Direction::TopLeft => Some((Direction::Top, Direction::Left)),
+            Direction::BottomRight => Some((Direction::Bottom, Direction::Right)),
+            Direction::BottomLeft => Some((Direction::Bottom, Direction::Left)),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Direction {
+    fn default() -> Self {
+        Self::None
+    }
+}
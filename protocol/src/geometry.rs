@@ -0,0 +1,251 @@
+//! Helpers for writing bots: map lookups, path finding, firing lines, aimed shots and danger maps.
+//! Movement follows the same rules as the server: a hero or a bullet can move to the
+//! neighbor cell if it is on the map and the cell is passable, a bullet flies over the water too,
+//! and a hero that steps on a teleport comes out of its pair. The paths count the steps only,
+//! the turns that the mud holds a hero and the pushes of the conveyors aren't in them.
+
+use std::collections::VecDeque;
+
+use super::{
+    direction::{Direction, Movement},
+    info::GameInfo,
+    map::{index, CanMove, Cell, Map},
+    pos::Pos,
+};
+
+impl Map {
+    /// Cell at the position, `None` when the position is out of the map.
+    pub fn try_get_cell(&self, pos: Pos) -> Option<Cell> {
+        if !self.pos_in_map(pos) {
+            return None;
+        }
+        Some(self.get_cell(pos))
+    }
+
+    fn passable(&self, from: Pos, direction: Direction) -> bool {
+        matches!(self.can_move(from, direction), CanMove::Yes)
+    }
+
+    fn flyable(&self, from: Pos, direction: Direction) -> bool {
+        matches!(self.can_fly(from, direction), CanMove::Yes)
+    }
+
+    /// Cells where a step from the position ends with the directions of the steps,
+    /// a step on a teleport ends at its pair.
+    pub fn neighbors(&self, pos: Pos, movement: Movement) -> Vec<(Direction, Pos)> {
+        movement
+            .directions()
+            .iter()
+            .filter(|direction| self.passable(pos, **direction))
+            .filter_map(|direction| Some((*direction, pos.shift(*direction)?)))
+            .map(|(direction, next)| (direction, self.teleport_exit(next).unwrap_or(next)))
+            .collect()
+    }
+
+    /// Distances from `from` to every reachable cell, the cells from `occupied` can't be passed.
+    pub fn distance_map(&self, from: Pos, occupied: &[Pos], movement: Movement) -> DistanceMap {
+        let cells = self.size as usize * self.size as usize;
+        let mut distances = vec![None; cells];
+        let mut steps = vec![None; cells];
+        let mut queue = VecDeque::new();
+        if self.pos_in_map(from) {
+            distances[index(self.size, from)] = Some(0);
+            queue.push_back(from);
+        }
+        while let Some(pos) = queue.pop_front() {
+            let distance = distances[index(self.size, pos)].unwrap_or(0);
+            for (direction, next) in self.neighbors(pos, movement) {
+                let ind = index(self.size, next);
+                if distances[ind].is_none() && !occupied.contains(&next) {
+                    distances[ind] = Some(distance + 1);
+                    steps[ind] = Some((direction, pos));
+                    queue.push_back(next);
+                }
+            }
+        }
+        DistanceMap {
+            size: self.size,
+            distances,
+            steps,
+        }
+    }
+
+    /// Direction of the shot that hits `to` from `from`: the target has to be on the same
+    /// row or column, or on the same diagonal with `Movement::EightWay`, within the `range`
+    /// and there must be no impassable cells between them.
+    pub fn firing_line(
+        &self,
+        from: Pos,
+        to: Pos,
+        range: u8,
+        movement: Movement,
+    ) -> Option<Direction> {
+        let (dx, dy) = (from.x.abs_diff(to.x), from.y.abs_diff(to.y));
+        if from == to || (dx != 0 && dy != 0 && dx != dy) {
+            return None;
+        }
+        let direction = *aim(from, to).first()?;
+        if !movement.allows(direction) || dx.max(dy) > range {
+            return None;
+        }
+        let mut pos = from;
+        while pos != to {
+            if !self.flyable(pos, direction) {
+                return None;
+            }
+            pos = pos.shift(direction)?;
+        }
+        Some(direction)
+    }
+
+    /// Cells that a bullet shot from `from` flies through before it stops.
+    pub fn shot_path(&self, from: Pos, direction: Direction, range: u8) -> Vec<Pos> {
+        let mut path = Vec::new();
+        let mut pos = from;
+        for _ in 0..range {
+            match pos.shift(direction) {
+                Some(next) if self.flyable(pos, direction) => {
+                    path.push(next);
+                    pos = next;
+                }
+                _ => break,
+            }
+        }
+        path
+    }
+
+    /// Cells that a bullet shot by `Action::AttackAt` flies through: it stops at the target,
+    /// at the first of the `occupied` cells, before an impassable cell or after `range` cells.
+    pub fn aimed_shot_path(&self, from: Pos, target: Pos, range: u8, occupied: &[Pos]) -> Vec<Pos> {
+        let mut path = Vec::new();
+        let mut pos = from;
+        for direction in aim(from, target).into_iter().take(range as usize) {
+            match pos.shift(direction) {
+                Some(next) if self.flyable(pos, direction) => {
+                    path.push(next);
+                    pos = next;
+                }
+                _ => break,
+            }
+            if occupied.contains(&pos) {
+                break;
+            }
+        }
+        path
+    }
+}
+
+/// Steps of the discrete line from `from` to `to` by the Bresenham algorithm,
+/// a step is diagonal when both coordinates change.
+pub fn aim(from: Pos, to: Pos) -> Vec<Direction> {
+    let (mut x, mut y) = (from.x as i16, from.y as i16);
+    let (to_x, to_y) = (to.x as i16, to.y as i16);
+    let (dx, dy) = ((to_x - x).abs(), -(to_y - y).abs());
+    let (sx, sy) = ((to_x - x).signum(), (to_y - y).signum());
+    let mut err = dx + dy;
+    let mut steps = Vec::new();
+    while (x, y) != (to_x, to_y) {
+        let (mut step_x, mut step_y) = (0, 0);
+        let double_err = 2 * err;
+        if double_err >= dy {
+            err += dy;
+            x += sx;
+            step_x = sx;
+        }
+        if double_err <= dx {
+            err += dx;
+            y += sy;
+            step_y = sy;
+        }
+        steps.push(match (step_x, step_y) {
+            (0, 1) => Direction::Top,
+            (0, -1) => Direction::Bottom,
+            (1, 0) => Direction::Right,
+            (-1, 0) => Direction::Left,
+            (1, 1) => Direction::TopRight,
+            (-1, 1) => Direction::TopLeft,
+            (1, -1) => Direction::BottomRight,
+            _ => Direction::BottomLeft,
+        });
+    }
+    steps
+}
+
+pub struct DistanceMap {
+    size: u8,
+    distances: Vec<Option<u16>>,
+    /// Step that reaches the cell first and the cell it starts from.
+    steps: Vec<Option<(Direction, Pos)>>,
+}
+
+impl DistanceMap {
+    /// Number of moves to the position, `None` when it can't be reached.
+    pub fn get(&self, pos: Pos) -> Option<u16> {
+        if pos.x >= self.size || pos.y >= self.size {
+            return None;
+        }
+        self.distances[index(self.size, pos)]
+    }
+
+    /// Moves that lead to the position by the shortest path.
+    pub fn path_to(&self, to: Pos) -> Option<Vec<Direction>> {
+        let distance = self.get(to)?;
+        let mut path = Vec::with_capacity(distance as usize);
+        let mut pos = to;
+        while let Some((direction, prev)) = self.steps[index(self.size, pos)] {
+            path.push(direction);
+            pos = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Positions of the living heroes that are visible on the map.
+pub fn occupied(info: &GameInfo) -> Vec<Pos> {
+    info.players
+        .iter()
+        .filter(|pl| pl.character.health > 0)
+        .filter_map(|pl| pl.character.pos)
+        .collect()
+}
+
+pub struct DangerMap {
+    size: u8,
+    damage: Vec<u16>,
+}
+
+impl DangerMap {
+    /// Damage that the enemies of your hero with loaded guns can deal to the cells by one
+    /// shot each, in the state of a spectator every hero counts.
+    pub fn new(info: &GameInfo) -> Self {
+        let size = info.map.size;
+        let mut damage = vec![0; size as usize * size as usize];
+        let me = info.my_player();
+        for (ind, pl) in info.players.iter().enumerate() {
+            let enemy = &pl.character;
+            if info.me == Some(ind)
+                || me.is_some_and(|me| pl.is_ally(me))
+                || enemy.health == 0
+                || enemy.gun_reloading_await > 0
+            {
+                continue;
+            }
+            if let Some(pos) = enemy.pos {
+                for direction in info.movement.directions().iter().copied() {
+                    for cell in info.map.shot_path(pos, direction, enemy.bullet_range) {
+                        damage[index(size, cell)] += enemy.bullet_damage as u16;
+                    }
+                }
+            }
+        }
+        Self { size, damage }
+    }
+
+    pub fn get(&self, pos: Pos) -> u16 {
+        if pos.x >= self.size || pos.y >= self.size {
+            return 0;
+        }
+        self.damage[index(self.size, pos)]
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ability::{Cooldowns, Mine},
+    direction::Movement,
+    map::Map,
+    objective::ObjectiveInfo,
+    pickup::Pickup,
+    pos::Pos,
+    zone::ZoneInfo,
+};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "boost")]
+pub enum BoostKind {
+    Speed { bonus: u8 },
+    Damage { bonus: u8 },
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Boost {
+    pub kind: BoostKind,
+    pub turns_left: u8,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CharacterInfo {
+    /// Name of the class, `None` for the characters made without a class.
+    #[serde(default)]
+    pub class: Option<String>,
+    #[serde(default)]
+    pub speed: u8,
+    pub health: u8,
+    #[serde(default)]
+    pub max_health: u8,
+    pub gun_reloading_await: u8,
+    #[serde(default)]
+    pub reload_time: u8,
+    pub bullet_damage: u8,
+    pub bullet_range: u8,
+    pub pos: Option<Pos>,
+    pub boosts: Vec<Boost>,
+    #[serde(default)]
+    pub cooldowns: Cooldowns,
+    /// Damage that the shield still blocks.
+    #[serde(default)]
+    pub shield: u8,
+    /// Turns left of the reveal.
+    #[serde(default)]
+    pub reveal_turns: u8,
+    /// Turns until the hero can move out of the mud.
+    #[serde(default)]
+    pub mud_turns: u8,
+    /// The hero hides in the bushes, the other fields are zeros and its health isn't
+    /// zero because it's dead.
+    #[serde(default)]
+    pub concealed: bool,
+}
+
+impl CharacterInfo {
+    pub fn without_pos(&mut self) {
+        self.pos = None;
+    }
+
+    /// Hides everything about the hero.
+    pub fn conceal(&mut self) {
+        *self = Self {
+            concealed: true,
+            ..Self::default()
+        };
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerInfo {
+    pub character: CharacterInfo,
+    /// Team of the player in team games, `None` in free-for-all.
+    #[serde(default)]
+    pub team: Option<u8>,
+}
+
+impl PlayerInfo {
+    pub fn without_pos(&mut self) {
+        self.character.without_pos();
+    }
+
+    pub fn conceal(&mut self) {
+        self.character.conceal();
+    }
+
+    /// Both players are in the same team.
+    pub fn is_ally(&self, other: &PlayerInfo) -> bool {
+        self.team.is_some() && self.team == other.team
+    }
+}
+
+impl Display for PlayerInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Player Info: {:?}", self.character)
+    }
+}
+
+/// Game state that the server sends to the clients every turn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameInfo {
+    pub map: Map,
+    pub players: Vec<PlayerInfo>,
+    pub pickups: Vec<Pickup>,
+    pub zone: Option<ZoneInfo>,
+    pub objective: Option<ObjectiveInfo>,
+    /// Mines of the player and the allies.
+    #[serde(default)]
+    pub mines: Vec<Mine>,
+    /// Whether the diagonal directions can be used.
+    #[serde(default)]
+    pub movement: Movement,
+    /// Whether `Action::AttackAt` can be used.
+    #[serde(default)]
+    pub free_aim: bool,
+    /// Index of your hero in `players`, `None` in the state of a spectator.
+    #[serde(default)]
+    pub me: Option<usize>,
+}
+
+impl GameInfo {
+    /// Your hero, `None` in the state of a spectator.
+    pub fn my_player(&self) -> Option<&PlayerInfo> {
+        self.players.get(self.me?)
+    }
+}
+
+impl Display for GameInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.map)
+    }
+}
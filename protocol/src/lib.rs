@@ -0,0 +1,16 @@
+//! Types shared by the server and the clients: everything that goes through the websocket,
+//! the map and the movement rules.
+
+pub mod ability;
+pub mod class;
+pub mod direction;
+pub mod geometry;
+pub mod info;
+pub mod map;
+pub mod message;
+pub mod objective;
+pub mod pickup;
+pub mod pos;
+pub mod result;
+pub mod stats;
+pub mod zone;
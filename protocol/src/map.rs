@@ -0,0 +1,289 @@
+use ez_colorize::ColorizeDisplay;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+use super::{direction::Direction, pos::Pos};
+
+/// Turns until a hero that stepped into the mud can move again, so it moves every other turn.
+pub const MUD_TURNS: u8 = 2;
+
+/// Square field of cells, the cell `(x, y)` is stored at the index `x * size + y`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Map {
+    pub field: Vec<Cell>,
+    pub size: u8,
+}
+
+pub struct MutMap {
+    pub field: Vec<Cell>,
+    size: u8,
+}
+
+impl MutMap {
+    pub const fn get_size(&self) -> u8 {
+        self.size
+    }
+
+    pub fn get_cell(&self, pos: Pos) -> Cell {
+        self.field[index(self.size, pos)]
+    }
+
+    pub fn immut_map(self) -> Map {
+        Map {
+            field: self.field,
+            size: self.size,
+        }
+    }
+}
+
+pub(crate) fn index(size: u8, pos: Pos) -> usize {
+    pos.x as usize * size as usize + pos.y as usize
+}
+
+impl Map {
+    pub fn new_empty(size: u8) -> Self {
+        Self {
+            size,
+            field: vec![Cell::Empty; size as usize * size as usize],
+        }
+    }
+
+    pub fn clone_mut_map(&self) -> MutMap {
+        MutMap {
+            field: self.field.clone(),
+            size: self.get_size(),
+        }
+    }
+
+    pub const fn get_size(&self) -> u8 {
+        self.size
+    }
+
+    pub fn get_cell(&self, pos: Pos) -> Cell {
+        self.field[index(self.size, pos)]
+    }
+
+    pub fn set_cell(&mut self, pos: Pos, cell: Cell) {
+        self.field[index(self.size, pos)] = cell;
+    }
+
+    /// Deals damage to a destructible cell, a destroyed wall becomes `Cell::Empty`.
+    pub fn damage_cell(&mut self, pos: Pos, damage: u8) {
+        if let Cell::DestructibleWall { health } = self.get_cell(pos) {
+            if health > damage {
+                self.set_cell(
+                    pos,
+                    Cell::DestructibleWall {
+                        health: health - damage,
+                    },
+                );
+            } else {
+                self.set_cell(pos, Cell::Empty);
+            }
+        }
+    }
+
+    pub fn pos_in_map(&self, pos: Pos) -> bool {
+        let (x, y) = pos.into();
+        return x < self.size && y < self.size;
+    }
+
+    /// The field has a cell for every position, a map file could have less or more.
+    pub fn is_valid(&self) -> bool {
+        self.field.len() == self.size as usize * self.size as usize
+    }
+
+    /// A hero steps to the cell, a diagonal step is blocked by an impassable cell on either
+    /// side of the corner.
+    pub fn can_move(&self, from: Pos, direction: Direction) -> CanMove {
+        self.step(from, direction, false)
+    }
+
+    /// A bullet flies to the cell, by the rules of `can_move` except that the water doesn't
+    /// stop it and the conveyors don't matter.
+    pub fn can_fly(&self, from: Pos, direction: Direction) -> CanMove {
+        self.step(from, direction, true)
+    }
+
+    /// Cell that stops the step of a hero, the corner cells of a diagonal are checked first.
+    pub fn obstacle(&self, from: Pos, direction: Direction) -> Option<Pos> {
+        self.blocker(from, direction, false)
+    }
+
+    /// Cell that stops the bullet, like `obstacle`.
+    pub fn bullet_obstacle(&self, from: Pos, direction: Direction) -> Option<Pos> {
+        self.blocker(from, direction, true)
+    }
+
+    /// The other teleport of the same channel when `pos` is a teleport.
+    pub fn teleport_exit(&self, pos: Pos) -> Option<Pos> {
+        let channel = match self.get_cell(pos) {
+            Cell::Teleport { channel } => channel,
+            _ => return None,
+        };
+        self.field
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| **cell == Cell::Teleport { channel })
+            .map(|(ind, _)| {
+                Pos::new(
+                    (ind / self.size as usize) as u8,
+                    (ind % self.size as usize) as u8,
+                )
+            })
+            .find(|exit| *exit != pos)
+    }
+
+    fn step(&self, from: Pos, direction: Direction, bullet: bool) -> CanMove {
+        if let Some(pos) = self
+            .blocker(from, direction, bullet)
+            .filter(|_| direction.is_diagonal())
+        {
+            return CanMove::No(WhyDontCanMove::ImpassableObject {
+                obj: self.get_cell(pos),
+            });
+        }
+        match from.shift(direction) {
+            None => CanMove::No(WhyDontCanMove::PosOverflow),
+            Some(pos) if !self.pos_in_map(pos) => CanMove::No(WhyDontCanMove::OutOfRange),
+            Some(pos) => {
+                let cell = self.get_cell(pos);
+                if bullet && cell.can_fly()
+                    || !bullet && cell.can_move() && cell.can_enter(direction)
+                {
+                    CanMove::Yes
+                } else {
+                    CanMove::No(WhyDontCanMove::ImpassableObject { obj: cell })
+                }
+            }
+        }
+    }
+
+    fn blocker(&self, from: Pos, direction: Direction, bullet: bool) -> Option<Pos> {
+        let corners = direction
+            .components()
+            .map_or(Vec::new(), |(vertical, horizontal)| {
+                vec![vertical, horizontal]
+            });
+        corners
+            .into_iter()
+            .chain([direction])
+            .filter_map(|direction| from.shift(direction))
+            .filter(|pos| self.pos_in_map(*pos))
+            .find(|pos| {
+                let cell = self.get_cell(*pos);
+                !(bullet && cell.can_fly() || !bullet && cell.can_move())
+            })
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for x in 0..self.size {
+            for y in 0..self.size {
+                match self.get_cell((x, y).into()) {
+                    Cell::Player { .. } => {
+                        write!(f, "{} ", "Player".green())?;
+                    }
+                    Cell::Bullet => {
+                        write!(f, "{} ", "Bullet".red())?;
+                    }
+                    _ => {
+                        write!(f, "{:?} ", self.get_cell((x, y).into()))?;
+                    }
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+pub enum CanMove {
+    Yes,
+    No(WhyDontCanMove),
+}
+
+pub enum WhyDontCanMove {
+    ImpassableObject { obj: Cell },
+    OutOfRange,
+    PosOverflow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Cell {
+    Bushes,
+    Empty,
+    Wall,
+    Player,
+    Bot,
+    Bullet,
+    DestructibleWall {
+        health: u8,
+    },
+    /// Stops the heroes, the bullets fly over it.
+    Water,
+    /// Slows a hero to one move every other turn: a move into the mud ends there and the hero
+    /// waits for the turn after the next one, the bullets fly over it.
+    Mud,
+    /// A hero that steps on it comes out of the other teleport of the channel.
+    Teleport {
+        channel: u8,
+    },
+    /// Can't be entered against the `direction`, it moves the heroes on it by one cell
+    /// in the `direction` at the end of every turn.
+    Conveyor {
+        direction: Direction,
+    },
+}
+
+impl Cell {
+    pub const fn can_move(&self) -> bool {
+        match self {
+            Cell::Bot => false,
+            Cell::Bullet => true,
+            Cell::Bushes => true,
+            Cell::Empty => true,
+            Cell::Player => false,
+            Cell::Wall => false,
+            Cell::DestructibleWall { .. } => false,
+            Cell::Water => false,
+            Cell::Mud => true,
+            Cell::Teleport { .. } => true,
+            Cell::Conveyor { .. } => true,
+        }
+    }
+
+    pub const fn can_fly(&self) -> bool {
+        match self {
+            Cell::Water => true,
+            _ => self.can_move(),
+        }
+    }
+
+    /// A conveyor stops the steps with a part against its direction.
+    pub fn can_enter(&self, direction: Direction) -> bool {
+        let against = match self {
+            Cell::Conveyor { direction } => direction.opposite(),
+            _ => return true,
+        };
+        let (vertical, horizontal) = direction.components().unwrap_or((direction, direction));
+        vertical != against && horizontal != against
+    }
+
+    pub const fn as_u8(&self) -> u8 {
+        match self {
+            Cell::Bot => 0,
+            Cell::Bullet => 1,
+            Cell::Bushes => 2,
+            Cell::Empty => 3,
+            Cell::Player => 4,
+            Cell::Wall => 5,
+            Cell::DestructibleWall { .. } => 6,
+            Cell::Water => 7,
+            Cell::Mud => 8,
+            Cell::Teleport { .. } => 9,
+            Cell::Conveyor { .. } => 10,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+use super::{direction::Direction, pos::Pos, result::GameResult};
+
+/// The first message of the server after the websocket handshake.
+pub const ACCEPT_CONNECTION: &str = "Accept connection";
+
+/// Answer to the handshake with a class that the server doesn't have, the connection is closed.
+pub const UNKNOWN_CLASS: &str = "Unknown class";
+
+/// The server is stopping, it sends this to the clients before closing their connections.
+pub const SERVER_SHUTDOWN: &str = "Server shutdown";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "action")]
+pub enum Action {
+    Move {
+        direction: Direction,
+        range: u8,
+    },
+    Attack {
+        direction: Direction,
+    },
+    Reload,
+    Nothing,
+    /// Shoots along the Bresenham line to the cell, only in the games with `free_aim`.
+    AttackAt {
+        target: Pos,
+    },
+    /// Moves `DASH_RANGE` cells at once whatever the speed is.
+    Dash {
+        direction: Direction,
+    },
+    /// Blocks the next `SHIELD_AMOUNT` damage.
+    Shield,
+    /// Leaves a mine on the cell of the hero.
+    PlaceMine,
+    /// Shows the enemies in the bushes for `REVEAL_TURNS` turns.
+    Reveal,
+}
+
+/// Actions of the client for the turn.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Response {
+    pub actions: Vec<Action>,
+}
+
+impl Response {
+    pub fn new(actions: &[Action]) -> Self {
+        Self {
+            actions: Vec::from(actions),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    Win,
+    Lose,
+    Draw,
+}
+
+/// Last message of the match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchEnd {
+    pub outcome: Outcome,
+    pub result: GameResult,
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use super::{pos::Pos, zone::Bounds};
+
+/// Flag of a team in the capture-the-flag mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Flag {
+    pub team: u8,
+    /// Where the flag stands when nobody took it.
+    pub base: Pos,
+    pub pos: Pos,
+    /// Id of the player that carries the flag.
+    pub carrier: Option<u8>,
+}
+
+impl Flag {
+    pub fn at_base(&self) -> bool {
+        self.carrier.is_none() && self.pos == self.base
+    }
+}
+
+/// Points of a team or a player.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Points {
+    pub id: u8,
+    pub points: u16,
+}
+
+/// Kills and deaths of a player in the deathmatch.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct KillDeath {
+    pub id: u8,
+    pub kills: u16,
+    pub deaths: u16,
+}
+
+/// Dead player that comes back in `turns_left` turns.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Respawn {
+    pub id: u8,
+    pub turns_left: u8,
+}
+
+/// Objective of the match, `None` in the game state means elimination.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ObjectiveInfo {
+    /// Teams bring the enemy flags to their own base, `captures` are by team id.
+    CaptureTheFlag {
+        flags: Vec<Flag>,
+        captures: Vec<Points>,
+        captures_to_win: u16,
+    },
+    /// Players earn a point every turn they hold the hill without enemies in it,
+    /// `points` are by player id, a team plays for the sum of its members.
+    KingOfTheHill {
+        hill: Bounds,
+        points: Vec<Points>,
+        points_to_win: u16,
+    },
+    /// Killed players come back at a spawn point, a team plays for the sum of its members.
+    Deathmatch {
+        scores: Vec<KillDeath>,
+        respawns: Vec<Respawn>,
+        kills_to_win: u16,
+    },
+}
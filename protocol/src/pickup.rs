@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use super::pos::Pos;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum PickupKind {
+    Health { amount: u8 },
+    Ammo,
+    Speed { bonus: u8, duration: u8 },
+    Damage { bonus: u8, duration: u8 },
+}
+
+/// Spawn point of a pickup, after being picked up it appears again in `respawn_time` turns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pickup {
+    pub pos: Pos,
+    pub kind: PickupKind,
+    pub respawn_time: u8,
+    #[serde(default)]
+    pub respawn_awaiting: u8,
+}
+
+impl Pickup {
+    pub const fn new(pos: Pos, kind: PickupKind, respawn_time: u8) -> Self {
+        Self {
+            pos,
+            kind,
+            respawn_time,
+            respawn_awaiting: 0,
+        }
+    }
+
+    pub fn available(&self) -> bool {
+        self.respawn_awaiting == 0
+    }
+
+    pub fn pick_up(&mut self) -> Option<PickupKind> {
+        if self.available() {
+            self.respawn_awaiting = self.respawn_time;
+            return Some(self.kind);
+        }
+        None
+    }
+
+    pub fn respawn_update(&mut self) {
+        if !self.available() {
+            self.respawn_awaiting -= 1;
+        }
+    }
+}
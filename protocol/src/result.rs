@@ -0,0 +1,143 @@
+use std::cmp::Reverse;
+
+use serde::{Deserialize, Serialize};
+
+use super::stats::PlayerStats;
+
+/// Rule that ranks the players still alive when the time is over.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum Tiebreak {
+    #[default]
+    Health,
+    DamageDealt,
+    Kills,
+    /// All living players share the first place, nobody wins.
+    Draw,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rank {
+    pub id: u8,
+    pub place: u8,
+    pub score: u16,
+    pub alive: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamRank {
+    pub team: u8,
+    pub place: u8,
+    /// Sum of the scores of the members.
+    pub score: u16,
+    /// Members still alive.
+    pub alive: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameResult {
+    pub winners: Vec<u8>,
+    /// Living players sharing the first place, the match is a draw for them.
+    #[serde(default)]
+    pub draw: Vec<u8>,
+    pub ranking: Vec<Rank>,
+    /// Ranking of the teams, empty in free-for-all.
+    #[serde(default)]
+    pub teams: Vec<TeamRank>,
+    pub stats: Vec<PlayerStats>,
+}
+
+impl GameResult {
+    /// Living players are ranked above dead ones, then by score,
+    /// players with the same score share a place. Only a living player alone
+    /// in the first place wins, several of them make a draw.
+    pub fn new(mut players: Vec<(u8, bool, u16)>, stats: Vec<PlayerStats>) -> Self {
+        players.sort_by_key(|(_, alive, score)| Reverse((*alive, *score)));
+        let mut ranking: Vec<Rank> = Vec::with_capacity(players.len());
+        for (ind, (id, alive, score)) in players.into_iter().enumerate() {
+            let place = match ranking.last() {
+                Some(last) if last.alive == alive && last.score == score => last.place,
+                _ => ind as u8 + 1,
+            };
+            ranking.push(Rank {
+                id,
+                place,
+                score,
+                alive,
+            });
+        }
+        let first: Vec<u8> = ranking
+            .iter()
+            .filter(|rank| rank.alive && rank.place == 1)
+            .map(|rank| rank.id)
+            .collect();
+        let (winners, draw) = split_first(first);
+        Self {
+            winners,
+            draw,
+            ranking,
+            teams: Vec::new(),
+            stats,
+        }
+    }
+
+    /// Ranks the teams of the `(player id, team)` pairs the same way as the players: teams
+    /// with living members first, then by score. All members of the first team win,
+    /// the dead ones too, players without a team can't win a team game.
+    /// Several teams in the first place make a draw for their members.
+    pub fn with_teams(mut self, members: &[(u8, u8)]) -> Self {
+        let mut teams: Vec<(u8, u8, u16)> = Vec::new();
+        for rank in self.ranking.iter() {
+            if let Some((_, team)) = members.iter().find(|(id, _)| *id == rank.id) {
+                let ind = match teams.iter().position(|(t, _, _)| t == team) {
+                    Some(ind) => ind,
+                    None => {
+                        teams.push((*team, 0, 0));
+                        teams.len() - 1
+                    }
+                };
+                teams[ind].1 += rank.alive as u8;
+                teams[ind].2 += rank.score;
+            }
+        }
+        teams.sort_by_key(|(_, alive, score)| Reverse((*alive > 0, *score)));
+        self.teams.clear();
+        for (ind, (team, alive, score)) in teams.into_iter().enumerate() {
+            let place = match self.teams.last() {
+                Some(last) if (last.alive > 0) == (alive > 0) && last.score == score => last.place,
+                _ => ind as u8 + 1,
+            };
+            self.teams.push(TeamRank {
+                team,
+                place,
+                score,
+                alive,
+            });
+        }
+        let first_teams: Vec<u8> = self
+            .teams
+            .iter()
+            .filter(|rank| rank.alive > 0 && rank.place == 1)
+            .map(|rank| rank.team)
+            .collect();
+        let members_of = |teams: Vec<u8>| -> Vec<u8> {
+            members
+                .iter()
+                .filter(|(_, team)| teams.contains(team))
+                .map(|(id, _)| *id)
+                .collect()
+        };
+        let (winning_teams, draw_teams) = split_first(first_teams);
+        self.winners = members_of(winning_teams);
+        self.draw = members_of(draw_teams);
+        self
+    }
+}
+
+/// One side in the first place wins, several share a draw.
+fn split_first(first: Vec<u8>) -> (Vec<u8>, Vec<u8>) {
+    if first.len() == 1 {
+        (first, Vec::new())
+    } else {
+        (Vec::new(), first)
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub id: u8,
+    pub shots_fired: u16,
+    pub hits: u16,
+    pub damage_dealt: u16,
+    pub damage_taken: u16,
+    pub kills: u16,
+    #[serde(default)]
+    pub deaths: u16,
+    pub distance_moved: u16,
+    /// Turns with a `Reload` among the actions.
+    pub reloading_turns: u16,
+    /// Turns with nothing but `Nothing` in the actions.
+    pub idle_turns: u16,
+    pub invalid_actions: u16,
+    /// Number of the turn (starting from 0) when the player hit somebody for the first time.
+    pub first_hit_turn: Option<u16>,
+}
+
+impl PlayerStats {
+    pub fn new(id: u8) -> Self {
+        Self {
+            id,
+            ..Default::default()
+        }
+    }
+
+    /// Counts a hit on a player that had `health` before getting `damage`.
+    pub fn hit(&mut self, health: u8, damage: u8, turn: u16) {
+        self.hits += 1;
+        self.first_hit_turn.get_or_insert(turn);
+        self.damage_dealt += health.min(damage) as u16;
+        if health > 0 && damage >= health {
+            self.kills += 1;
+        }
+    }
+
+    pub fn got_damage(&mut self, health: u8, damage: u8) {
+        self.damage_taken += health.min(damage) as u16;
+        if health > 0 && damage >= health {
+            self.deaths += 1;
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use super::pos::Pos;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bounds {
+    pub min: Pos,
+    pub max: Pos,
+}
+
+impl Bounds {
+    pub const fn new(min: Pos, max: Pos) -> Self {
+        Self { min, max }
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        (self.min.x..=self.max.x).contains(&pos.x) && (self.min.y..=self.max.y).contains(&pos.y)
+    }
+
+    /// Bounds one cell smaller from every side, a zone of one cell does not shrink.
+    pub fn shrink(&self) -> Self {
+        let mut bounds = *self;
+        if bounds.min.x < bounds.max.x {
+            bounds.min.x += 1;
+            bounds.max.x -= 1;
+        }
+        if bounds.min.y < bounds.max.y {
+            bounds.min.y += 1;
+            bounds.max.y -= 1;
+        }
+        bounds
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZoneInfo {
+    pub current: Bounds,
+    pub next: Option<Bounds>,
+    pub turns_to_shrink: u16,
+    pub damage: u8,
+}
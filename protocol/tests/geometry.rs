@@ -0,0 +1,174 @@
+use protocol::{
+    direction::{Direction, Movement},
+    geometry::DangerMap,
+    info::GameInfo,
+    map::{Cell, Map},
+    pos::Pos,
+};
+
+const GAME_INFO: &str = include_str!("../../json_example/game_info.json");
+
+/// The example game with the enemy moved to `enemy`.
+fn game_with_enemy(enemy: Pos, movement: Movement) -> GameInfo {
+    let mut info: GameInfo = serde_json::from_str(GAME_INFO).unwrap();
+    info.players[1].character.pos = Some(enemy);
+    info.movement = movement;
+    info
+}
+
+#[test]
+fn paths_go_through_teleports() {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(1, 0), Cell::Teleport { channel: 0 });
+    map.set_cell(Pos::new(3, 4), Cell::Teleport { channel: 0 });
+    let distances = map.distance_map(Pos::new(0, 0), &[], Movement::FourWay);
+    assert_eq!(distances.get(Pos::new(3, 4)), Some(1));
+    assert_eq!(distances.get(Pos::new(4, 4)), Some(2));
+    assert_eq!(
+        distances.path_to(Pos::new(4, 4)),
+        Some(vec![Direction::Right, Direction::Right])
+    );
+}
+
+#[test]
+fn eight_way_paths_take_the_diagonals() {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(2, 1), Cell::Wall);
+    let from = Pos::new(0, 0);
+    let four_way = map.distance_map(from, &[], Movement::FourWay);
+    let eight_way = map.distance_map(from, &[], Movement::EightWay);
+    assert_eq!(four_way.get(Pos::new(4, 4)), Some(8));
+    assert_eq!(eight_way.get(Pos::new(1, 1)), Some(1));
+    assert_eq!(
+        eight_way.path_to(Pos::new(1, 1)),
+        Some(vec![Direction::TopRight])
+    );
+    // The diagonal from (1, 1) to (2, 2) cuts the corner of the wall
+    assert_eq!(eight_way.get(Pos::new(2, 2)), Some(3));
+    let mut pos = from;
+    for direction in eight_way.path_to(Pos::new(2, 2)).unwrap() {
+        assert!(map.obstacle(pos, direction).is_none());
+        pos = pos.shift(direction).unwrap();
+    }
+    assert_eq!(pos, Pos::new(2, 2));
+}
+
+#[test]
+fn eight_way_firing_lines_are_diagonal_too() {
+    let mut map = Map::new_empty(5);
+    let (from, to) = (Pos::new(0, 4), Pos::new(3, 1));
+    assert_eq!(map.firing_line(from, to, 5, Movement::FourWay), None);
+    assert_eq!(
+        map.firing_line(from, to, 5, Movement::EightWay),
+        Some(Direction::BottomRight)
+    );
+    assert_eq!(map.firing_line(from, to, 2, Movement::EightWay), None);
+    map.set_cell(Pos::new(2, 2), Cell::Wall);
+    assert_eq!(map.firing_line(from, to, 5, Movement::EightWay), None);
+}
+
+#[test]
+fn eight_way_danger_covers_the_diagonals() {
+    let enemy = Pos::new(2, 2);
+    let four_way = DangerMap::new(&game_with_enemy(enemy, Movement::FourWay));
+    let eight_way = DangerMap::new(&game_with_enemy(enemy, Movement::EightWay));
+    assert_eq!(four_way.get(Pos::new(4, 4)), 0);
+    assert_eq!(eight_way.get(Pos::new(4, 4)), 20);
+    assert_eq!(four_way.get(Pos::new(2, 4)), 20);
+    assert_eq!(eight_way.get(Pos::new(3, 4)), 0);
+}
+
+/// Walls at (1, 0) and (1, 1) and the bushes at (3, 1), a swapped x and y would move them
+/// to (0, 1), (1, 1) and (1, 3).
+fn lopsided() -> Map {
+    let mut map = Map::new_empty(5);
+    map.set_cell(Pos::new(1, 0), Cell::Wall);
+    map.set_cell(Pos::new(1, 1), Cell::Wall);
+    map.set_cell(Pos::new(3, 1), Cell::Bushes);
+    map
+}
+
+#[test]
+fn cells_are_looked_up_by_x_then_y() {
+    let map = lopsided();
+    assert_eq!(map.try_get_cell(Pos::new(1, 0)), Some(Cell::Wall));
+    assert_eq!(map.try_get_cell(Pos::new(0, 1)), Some(Cell::Empty));
+    assert_eq!(map.try_get_cell(Pos::new(3, 1)), Some(Cell::Bushes));
+    assert_eq!(map.try_get_cell(Pos::new(5, 0)), None);
+}
+
+#[test]
+fn neighbors_skip_walls_and_the_map_edge() {
+    let map = lopsided();
+    assert_eq!(
+        map.neighbors(Pos::new(0, 0), Movement::FourWay),
+        vec![(Direction::Top, Pos::new(0, 1))]
+    );
+}
+
+#[test]
+fn distances_go_around_walls_and_occupied_cells() {
+    let map = lopsided();
+    let from = Pos::new(0, 0);
+    let free = map.distance_map(from, &[], Movement::FourWay);
+    assert_eq!(free.get(Pos::new(2, 0)), Some(6));
+    assert_eq!(free.get(Pos::new(0, 2)), Some(2));
+    assert_eq!(free.get(Pos::new(1, 0)), None);
+    assert_eq!(
+        free.path_to(Pos::new(2, 1)),
+        Some(vec![
+            Direction::Top,
+            Direction::Top,
+            Direction::Right,
+            Direction::Right,
+            Direction::Bottom,
+        ])
+    );
+    let blocked = map.distance_map(from, &[Pos::new(0, 2)], Movement::FourWay);
+    assert_eq!(blocked.get(Pos::new(0, 2)), None);
+    assert_eq!(blocked.get(Pos::new(2, 0)), None);
+    assert_eq!(blocked.path_to(Pos::new(4, 4)), None);
+}
+
+#[test]
+fn firing_lines_stop_at_walls() {
+    let map = lopsided();
+    assert_eq!(
+        map.firing_line(Pos::new(0, 0), Pos::new(0, 3), 5, Movement::FourWay),
+        Some(Direction::Top)
+    );
+    assert_eq!(
+        map.firing_line(Pos::new(0, 0), Pos::new(3, 0), 5, Movement::FourWay),
+        None
+    );
+    assert_eq!(
+        map.firing_line(Pos::new(2, 0), Pos::new(4, 0), 5, Movement::FourWay),
+        Some(Direction::Right)
+    );
+    assert_eq!(
+        map.firing_line(Pos::new(0, 0), Pos::new(0, 3), 2, Movement::FourWay),
+        None
+    );
+    assert_eq!(
+        map.shot_path(Pos::new(3, 0), Direction::Left, 5),
+        vec![Pos::new(2, 0)]
+    );
+    assert_eq!(
+        map.shot_path(Pos::new(3, 0), Direction::Top, 2),
+        vec![Pos::new(3, 1), Pos::new(3, 2)]
+    );
+}
+
+#[test]
+fn danger_comes_from_loaded_enemy_guns() {
+    let mut info = game_with_enemy(Pos::new(2, 0), Movement::FourWay);
+    info.map = lopsided();
+    info.players[0].character.pos = Some(Pos::new(0, 4));
+    let danger = DangerMap::new(&info);
+    assert_eq!(danger.get(Pos::new(4, 0)), 20);
+    assert_eq!(danger.get(Pos::new(2, 4)), 20);
+    assert_eq!(danger.get(Pos::new(0, 0)), 0);
+    assert_eq!(danger.get(Pos::new(0, 2)), 0);
+    info.players[1].character.gun_reloading_await = 1;
+    assert_eq!(DangerMap::new(&info).get(Pos::new(4, 0)), 0);
+}
@@ -0,0 +1,64 @@
+use protocol::result::GameResult;
+
+fn places(result: &GameResult) -> Vec<(u8, u8)> {
+    result
+        .ranking
+        .iter()
+        .map(|rank| (rank.id, rank.place))
+        .collect()
+}
+
+#[test]
+fn best_living_player_wins() {
+    let result = GameResult::new(vec![(0, true, 20), (1, true, 50), (2, false, 90)], vec![]);
+    assert_eq!(places(&result), vec![(1, 1), (0, 2), (2, 3)]);
+    assert_eq!(result.winners, vec![1]);
+    assert!(result.draw.is_empty());
+}
+
+#[test]
+fn tie_for_the_first_place_is_a_draw() {
+    let result = GameResult::new(vec![(0, true, 40), (1, true, 40), (2, true, 10)], vec![]);
+    assert_eq!(places(&result), vec![(0, 1), (1, 1), (2, 3)]);
+    assert!(result.winners.is_empty());
+    assert_eq!(result.draw, vec![0, 1]);
+}
+
+#[test]
+fn tie_below_the_first_place_keeps_the_winner() {
+    let result = GameResult::new(vec![(0, true, 10), (1, true, 60), (2, true, 10)], vec![]);
+    assert_eq!(places(&result), vec![(1, 1), (0, 2), (2, 2)]);
+    assert_eq!(result.winners, vec![1]);
+    assert!(result.draw.is_empty());
+}
+
+#[test]
+fn dead_players_share_a_place_and_never_win() {
+    let result = GameResult::new(vec![(0, false, 0), (1, false, 0)], vec![]);
+    assert_eq!(places(&result), vec![(0, 1), (1, 1)]);
+    assert!(result.winners.is_empty());
+    assert!(result.draw.is_empty());
+}
+
+#[test]
+fn teams_tied_for_the_first_place_draw() {
+    let result = GameResult::new(
+        vec![(0, true, 30), (1, false, 0), (2, true, 10), (3, true, 20)],
+        vec![],
+    )
+    .with_teams(&[(0, 0), (1, 0), (2, 1), (3, 1)]);
+    assert!(result.teams.iter().all(|rank| rank.place == 1));
+    assert!(result.winners.is_empty());
+    assert_eq!(result.draw, vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn whole_first_team_wins() {
+    let result = GameResult::new(
+        vec![(0, true, 30), (1, false, 0), (2, true, 10), (3, false, 0)],
+        vec![],
+    )
+    .with_teams(&[(0, 0), (1, 0), (2, 1), (3, 1)]);
+    assert_eq!(result.winners, vec![0, 1]);
+    assert!(result.draw.is_empty());
+}
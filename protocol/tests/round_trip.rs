@@ -0,0 +1,119 @@
+use protocol::{
+    direction::Direction,
+    info::GameInfo,
+    map::Map,
+    message::{Action, MatchEnd, Response},
+    pickup::{Pickup, PickupKind},
+    pos::Pos,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+const GAME_INFO: &str = include_str!("../../json_example/game_info.json");
+const ACTION: &str = include_str!("../../json_example/action.json");
+const MATCH_END: &str = include_str!("../../json_example/match_end.json");
+const ARENA_MAP: &str = include_str!("../../server/maps/arena.json");
+const PICKUPS: &str = include_str!("../../server/pickups.json");
+
+/// Parses the JSON and serializes it back, nothing may be lost or renamed on the way.
+fn assert_round_trip<T: Serialize + DeserializeOwned>(json: &str) {
+    let parsed: T = serde_json::from_str(json).unwrap();
+    let expected: Value = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), expected);
+}
+
+#[test]
+fn game_info_example_round_trip() {
+    assert_round_trip::<GameInfo>(GAME_INFO);
+}
+
+#[test]
+fn action_example_round_trip() {
+    assert_round_trip::<Response>(ACTION);
+    let response: Response = serde_json::from_str(ACTION).unwrap();
+    assert_eq!(
+        response.actions,
+        vec![
+            Action::Move {
+                direction: Direction::Right,
+                range: 2
+            },
+            Action::Reload
+        ]
+    );
+}
+
+#[test]
+fn match_end_example_round_trip() {
+    assert_round_trip::<MatchEnd>(MATCH_END);
+}
+
+/// A map file is the map with the pickups of its games.
+#[test]
+fn arena_map_round_trip() {
+    let mut file: Value = serde_json::from_str(ARENA_MAP).unwrap();
+    let pickups = file.as_object_mut().unwrap().remove("pickups").unwrap();
+    assert_round_trip::<Map>(&file.to_string());
+    let map: Map = serde_json::from_value(file).unwrap();
+    assert!(map.is_valid());
+    let pickups: Vec<Pickup> = serde_json::from_value(pickups).unwrap();
+    assert_eq!(pickups.len(), 4);
+}
+
+#[test]
+fn default_pickups_have_every_kind() {
+    let pickups: Vec<Pickup> = serde_json::from_str(PICKUPS).unwrap();
+    let kinds: Vec<&str> = pickups
+        .iter()
+        .map(|pickup| match pickup.kind {
+            PickupKind::Health { .. } => "Health",
+            PickupKind::Ammo => "Ammo",
+            PickupKind::Speed { .. } => "Speed",
+            PickupKind::Damage { .. } => "Damage",
+        })
+        .collect();
+    assert_eq!(kinds, vec!["Health", "Ammo", "Speed", "Damage"]);
+    assert!(pickups.iter().all(|pickup| pickup.available()));
+}
+
+#[test]
+fn every_action_round_trip() {
+    let actions = [
+        Action::Move {
+            direction: Direction::Top,
+            range: 1,
+        },
+        Action::Attack {
+            direction: Direction::Left,
+        },
+        Action::Attack {
+            direction: Direction::BottomLeft,
+        },
+        Action::AttackAt {
+            target: Pos::new(3, 1),
+        },
+        Action::Reload,
+        Action::Nothing,
+        Action::Dash {
+            direction: Direction::Right,
+        },
+        Action::Shield,
+        Action::PlaceMine,
+        Action::Reveal,
+    ];
+    let json = serde_json::to_string(&Response::new(&actions)).unwrap();
+    let response: Response = serde_json::from_str(&json).unwrap();
+    assert_eq!(response.actions, actions);
+}
+
+#[test]
+fn direction_is_a_plain_string() {
+    let json = serde_json::to_value(Action::Attack {
+        direction: Direction::Bottom,
+    })
+    .unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"action": "Attack", "direction": "Bottom"})
+    );
+}
@@ -1,7 +1,166 @@
 mod server;
 
-use server::server::Server;
-fn main() {
-    let mut server = Server::<2>::new();
-    server.run();
+use std::{env, fs, process::ExitCode, time::Duration};
+
+use core::{bot::Difficulty, direction::Movement, map::Map, pickup::Pickup};
+use serde::Deserialize;
+use server::{
+    server::{Objective, Server, ServerError},
+    session::TickMode,
+};
+use tracing::error;
+use tracing_subscriber::EnvFilter;
+fn main() -> ExitCode {
+    // `RUST_LOG=debug` or `RUST_LOG=server=warn` changes what is logged
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .init();
+    let args: Vec<String> = env::args().skip(1).collect();
+    let tick_mode = if args.iter().any(|arg| arg == "--fast") {
+        TickMode::Fast(Duration::from_secs(2))
+    } else if args.iter().any(|arg| arg == "--step") {
+        TickMode::Step
+    } else {
+        TickMode::Fixed(Duration::from_secs(2))
+    };
+    let objective = if args.iter().any(|arg| arg == "--ctf") {
+        Objective::CaptureTheFlag { captures_to_win: 3 }
+    } else if args.iter().any(|arg| arg == "--koth") {
+        Objective::KingOfTheHill { points_to_win: 20 }
+    } else if args.iter().any(|arg| arg == "--deathmatch") {
+        Objective::Deathmatch {
+            kills_to_win: 10,
+            respawn_turns: 3,
+        }
+    } else {
+        Objective::Elimination
+    };
+    let movement = if args.iter().any(|arg| arg == "--diagonal") {
+        Movement::EightWay
+    } else {
+        Movement::FourWay
+    };
+    let free_aim = args.iter().any(|arg| arg == "--free-aim");
+    // `--map maps/arena.json` plays on the map from the file
+    let map_file = match args.iter().position(|arg| arg == "--map") {
+        Some(ind) => match load_map(args.get(ind + 1)) {
+            Ok(map_file) => Some(map_file),
+            Err(err) => {
+                error!("{}", err);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => None,
+    };
+    // `--zone 100,50,10` shrinks the safe zone from the turn 100 every 50 turns, 10 damage
+    let zone = match args.iter().position(|arg| arg == "--zone") {
+        Some(ind) => match parse_zone(args.get(ind + 1), objective) {
+            Ok(zone) => Some(zone),
+            Err(err) => {
+                error!("{}", err);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => None,
+    };
+    let teams = args.iter().any(|arg| arg == "--teams")
+        || matches!(objective, Objective::CaptureTheFlag { .. });
+    let result = if teams {
+        // 2v2, friendly fire with `--friendly-fire`
+        let friendly_fire = args.iter().any(|arg| arg == "--friendly-fire");
+        run(
+            Server::<4>::new().with_teams(2, friendly_fire),
+            tick_mode,
+            objective,
+            movement,
+            free_aim,
+            map_file,
+            zone,
+        )
+    } else {
+        run(
+            Server::<2>::new(),
+            tick_mode,
+            objective,
+            movement,
+            free_aim,
+            map_file,
+            zone,
+        )
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            error!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run<const PLAYERS: usize>(
+    mut server: Server<PLAYERS>,
+    tick_mode: TickMode,
+    objective: Objective,
+    movement: Movement,
+    free_aim: bool,
+    map_file: Option<MapFile>,
+    zone: Option<(u16, u16, u8)>,
+) -> Result<(), ServerError> {
+    if let Some(MapFile { map, pickups }) = map_file {
+        server = server.with_map(map);
+        if let Some(pickups) = pickups {
+            server = server.with_pickups(pickups);
+        }
+    }
+    if let Some((start_turn, shrink_every, damage)) = zone {
+        server = server.with_zone(start_turn, shrink_every, damage);
+    }
+    server = server
+        .with_bots(Difficulty::Medium, Duration::from_secs(30))
+        .with_tick_mode(tick_mode)
+        .with_objective(objective)
+        .with_movement(movement)
+        .with_free_aim(free_aim);
+    server.run()
+}
+
+/// The map of the game state with the spawn points of the pickups, without them the games
+/// have the default pickups.
+#[derive(Deserialize)]
+struct MapFile {
+    #[serde(flatten)]
+    map: Map,
+    pickups: Option<Vec<Pickup>>,
+}
+
+/// The heroes start on the bottom row from `x = 1`, the map needs a place for four of them,
+/// the server checks that the cells of the heroes, the pickups and the objective are passable.
+fn load_map(path: Option<&String>) -> Result<MapFile, String> {
+    let path = path.ok_or("The map file is missing after --map")?;
+    let json = fs::read_to_string(path).map_err(|err| format!("Can't read {}: {}", path, err))?;
+    let map_file: MapFile =
+        serde_json::from_str(&json).map_err(|err| format!("Can't parse {}: {}", path, err))?;
+    if !map_file.map.is_valid() || map_file.map.get_size() < 5 {
+        return Err(format!("The map in {} must be a square at least 5x5", path));
+    }
+    Ok(map_file)
+}
+
+/// `start,every,damage` of the safe zone, the heroes of a deathmatch respawn in the corners
+/// and the zone would kill them again and again.
+fn parse_zone(arg: Option<&String>, objective: Objective) -> Result<(u16, u16, u8), String> {
+    if matches!(objective, Objective::Deathmatch { .. }) {
+        return Err(String::from("The zone can't be used in a deathmatch"));
+    }
+    let arg = arg.ok_or("The zone settings are missing after --zone")?;
+    let invalid = || format!("The zone settings {} must be start,every,damage", arg);
+    let parts: Vec<&str> = arg.split(',').collect();
+    match parts[..] {
+        [start_turn, shrink_every, damage] => Ok((
+            start_turn.parse().map_err(|_| invalid())?,
+            shrink_every.parse().map_err(|_| invalid())?,
+            damage.parse().map_err(|_| invalid())?,
+        )),
+        _ => Err(invalid()),
+    }
 }
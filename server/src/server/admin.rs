@@ -0,0 +1,200 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    time::Duration,
+};
+
+use super::session::TickMode;
+use protocol::result::GameResult;
+use serde::{Deserialize, Serialize};
+
+/// Largest body of a request, a bigger one gets `400`.
+const MAX_BODY: usize = 64 * 1024;
+
+/// Local HTTP endpoint of the operator, it answers one JSON request per connection.
+pub struct Admin {
+    listener: TcpListener,
+}
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+#[derive(Serialize)]
+pub struct QueuedClient {
+    pub id: u64,
+    pub address: Option<String>,
+    pub class: Option<String>,
+    pub waiting_ms: u64,
+}
+
+#[derive(Clone, Serialize)]
+pub struct RunningGame {
+    pub id: u64,
+    pub turn: u16,
+    pub players: Vec<GamePlayer>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct GamePlayer {
+    pub client_id: u64,
+    pub hero_id: u8,
+}
+
+#[derive(Serialize)]
+pub struct FinishedGame {
+    pub id: u64,
+    pub result: GameResult,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Tick {
+    Fixed,
+    Fast,
+    Step,
+}
+
+/// Settings that the operator can change while the server is running.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Config {
+    pub tick: Tick,
+    /// Tick of the `Fixed` mode or timeout of the `Fast` mode, the `Step` mode ignores it.
+    pub tick_ms: u64,
+    pub queue_timeout_ms: u64,
+}
+
+impl Config {
+    pub fn new(tick_mode: TickMode, queue_timeout: Duration) -> Self {
+        let (tick, duration) = match tick_mode {
+            TickMode::Fixed(tick) => (Tick::Fixed, tick),
+            TickMode::Fast(timeout) => (Tick::Fast, timeout),
+            TickMode::Step => (Tick::Step, Duration::ZERO),
+        };
+        Self {
+            tick,
+            tick_ms: duration.as_millis() as u64,
+            queue_timeout_ms: queue_timeout.as_millis() as u64,
+        }
+    }
+
+    pub fn tick_mode(&self) -> TickMode {
+        let duration = Duration::from_millis(self.tick_ms);
+        match self.tick {
+            Tick::Fixed => TickMode::Fixed(duration),
+            Tick::Fast => TickMode::Fast(duration),
+            Tick::Step => TickMode::Step,
+        }
+    }
+
+    pub fn queue_timeout(&self) -> Duration {
+        Duration::from_millis(self.queue_timeout_ms)
+    }
+}
+
+impl Admin {
+    pub fn bind(addr: &str) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+        })
+    }
+
+    /// Waits for the next request, `None` if it's broken.
+    pub fn accept(&self) -> Option<(TcpStream, Request)> {
+        let (mut stream, _) = self.listener.accept().ok()?;
+        match read_request(&mut stream) {
+            Ok(request) => Some((stream, request)),
+            Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+                let _ = Response::error(400, &err.to_string()).send(&mut stream);
+                None
+            }
+            Err(_) => None,
+        }
+    }
+}
+
+impl Response {
+    pub fn json<T: Serialize>(value: &T) -> Self {
+        Self {
+            status: 200,
+            content_type: "application/json",
+            body: serde_json::to_string(value).unwrap(),
+        }
+    }
+
+    pub fn text(body: String) -> Self {
+        Self {
+            status: 200,
+            content_type: "text/plain; version=0.0.4",
+            body,
+        }
+    }
+
+    pub fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: serde_json::json!({ "error": message }).to_string(),
+        }
+    }
+
+    pub fn send(&self, stream: &mut TcpStream) -> io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            _ => "",
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason,
+            self.content_type,
+            self.body.len(),
+            self.body
+        )
+    }
+}
+
+fn read_request(stream: &mut TcpStream) -> io::Result<Request> {
+    stream.set_read_timeout(Some(Duration::from_millis(100)))?;
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut request_line = line.split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    if content_length > MAX_BODY {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("The body is larger than {} bytes", MAX_BODY),
+        ));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Request {
+        method,
+        path,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
@@ -1,26 +1,53 @@
 use std::net::TcpStream;
 
+use protocol::class::ClassStats;
+use tracing::{info_span, Span};
 use tungstenite::WebSocket;
 
-pub trait DynClient: WithHero + WithWS {
+pub trait DynClient: WithHero + WithWS + Send {
     fn add_hero_id(&mut self, hero_id: u8);
+    fn get_id(&self) -> u64;
+    /// Span of the connection, events about the client go into it.
+    fn get_span(&self) -> &Span;
+    /// Class picked in the handshake, `None` plays the default character.
+    fn get_class(&self) -> Option<&ClassStats>;
 }
 
-
 impl DynClient for Client {
     fn add_hero_id(&mut self, hero_id: u8) {
         self.hero_id = Some(hero_id);
     }
+
+    fn get_id(&self) -> u64 {
+        self.id
+    }
+
+    fn get_span(&self) -> &Span {
+        &self.span
+    }
+
+    fn get_class(&self) -> Option<&ClassStats> {
+        self.class.as_ref()
+    }
 }
 
 pub struct Client {
+    id: u64,
     ws: WebSocket<TcpStream>,
     hero_id: Option<u8>,
+    span: Span,
+    class: Option<ClassStats>,
 }
 
 impl Client {
-    pub fn new(ws: WebSocket<TcpStream>) -> Self {
-        Self { ws, hero_id: None }
+    pub fn new(id: u64, ws: WebSocket<TcpStream>, class: Option<ClassStats>) -> Self {
+        Self {
+            id,
+            ws,
+            hero_id: None,
+            class,
+            span: info_span!(parent: None, "client", id),
+        }
     }
 }
 
@@ -0,0 +1,139 @@
+use std::{
+    fmt::Write,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// Upper bounds of the turn latency buckets, in seconds.
+const LATENCY_BUCKETS: [f64; 10] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0];
+
+/// Counters of the server, exported in the Prometheus text format.
+/// They are shared by the threads of all games, so every counter is atomic.
+#[derive(Default)]
+pub struct Metrics {
+    pub connections: AtomicU64,
+    pub handshake_errors: AtomicU64,
+    pub games_started: AtomicU64,
+    pub games_finished: AtomicU64,
+    pub games_aborted: AtomicU64,
+    pub turns: AtomicU64,
+    /// Clients that didn't answer before the end of the turn.
+    pub timeouts: AtomicU64,
+    pub malformed_messages: AtomicU64,
+    pub disconnects: AtomicU64,
+    pub send_errors: AtomicU64,
+    latency_buckets: [AtomicU64; LATENCY_BUCKETS.len()],
+    latency_sum_micros: AtomicU64,
+    latency_count: AtomicU64,
+}
+
+impl Metrics {
+    /// Time from sending the state to the next turn.
+    pub fn observe_turn_latency(&self, latency: Duration) {
+        let seconds = latency.as_secs_f64();
+        for (bucket, bound) in self.latency_buckets.iter().zip(LATENCY_BUCKETS) {
+            if seconds <= bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.latency_sum_micros
+            .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+        self.latency_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn render(&self, active_games: usize, queued_clients: usize) -> String {
+        let mut text = String::new();
+        let gauges = [
+            ("active_games", "Games in progress", active_games as u64),
+            (
+                "queued_clients",
+                "Clients waiting for a game",
+                queued_clients as u64,
+            ),
+        ];
+        for (name, help, value) in gauges {
+            metric(&mut text, name, help, "gauge", value);
+        }
+        let counters = [
+            (
+                "connections_total",
+                "Accepted websocket connections",
+                &self.connections,
+            ),
+            (
+                "handshake_errors_total",
+                "Failed websocket handshakes",
+                &self.handshake_errors,
+            ),
+            ("games_started_total", "Started games", &self.games_started),
+            (
+                "games_finished_total",
+                "Games played to the end",
+                &self.games_finished,
+            ),
+            (
+                "games_aborted_total",
+                "Games stopped without a result",
+                &self.games_aborted,
+            ),
+            ("turns_total", "Played turns", &self.turns),
+            (
+                "timeouts_total",
+                "Clients that didn't answer in time",
+                &self.timeouts,
+            ),
+            (
+                "malformed_messages_total",
+                "Answers that aren't valid actions",
+                &self.malformed_messages,
+            ),
+            (
+                "disconnects_total",
+                "Clients that lost the connection during a game",
+                &self.disconnects,
+            ),
+            (
+                "send_errors_total",
+                "Messages that couldn't be sent",
+                &self.send_errors,
+            ),
+        ];
+        for (name, help, value) in counters {
+            metric(
+                &mut text,
+                name,
+                help,
+                "counter",
+                value.load(Ordering::Relaxed),
+            );
+        }
+        let name = "server_turn_latency_seconds";
+        let count = self.latency_count.load(Ordering::Relaxed);
+        let sum = self.latency_sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        let _ = writeln!(
+            text,
+            "# HELP {} Time from sending the state to the next turn",
+            name
+        );
+        let _ = writeln!(text, "# TYPE {} histogram", name);
+        for (bucket, bound) in self.latency_buckets.iter().zip(LATENCY_BUCKETS) {
+            let _ = writeln!(
+                text,
+                "{}_bucket{{le=\"{}\"}} {}",
+                name,
+                bound,
+                bucket.load(Ordering::Relaxed)
+            );
+        }
+        let _ = writeln!(text, "{}_bucket{{le=\"+Inf\"}} {}", name, count);
+        let _ = writeln!(text, "{}_sum {}", name, sum);
+        let _ = writeln!(text, "{}_count {}", name, count);
+        text
+    }
+}
+
+fn metric(text: &mut String, name: &str, help: &str, kind: &str, value: u64) {
+    let _ = writeln!(text, "# HELP server_{} {}", name, help);
+    let _ = writeln!(text, "# TYPE server_{} {}", name, kind);
+    let _ = writeln!(text, "server_{} {}", name, value);
+}
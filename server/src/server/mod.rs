@@ -1,2 +1,5 @@
+pub mod admin;
+pub mod client;
+pub mod metrics;
 pub mod server;
-pub mod client;
\ No newline at end of file
+pub mod session;
@@ -1,173 +1,816 @@
 use std::{
     collections::VecDeque,
+    fmt::Display,
+    io::{self, stdin},
     net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{channel, Receiver, Sender},
+        Arc, Condvar, Mutex, PoisonError,
+    },
+    thread,
     time::{Duration, Instant},
 };
 
-use std::thread::sleep;
-
-use super::client::{Client, DynClient};
+use super::{
+    admin::{Admin, Config, FinishedGame, QueuedClient, Request, Response, RunningGame},
+    client::{Client, DynClient},
+    metrics::Metrics,
+    session::{Command, Session, TickMode},
+};
 use core::{
-    direction::Direction,
-    game::{GameBuilder, GameState, LikeGame},
-    player::{Action, DynPlayer, Player},
+    bot::Difficulty,
+    character::Character,
+    direction::{Direction, Movement},
+    game::{GameBuilder, LikeGame},
+    map::Map,
+    mode::{CaptureTheFlag, Deathmatch, Elimination, GameMode, KingOfTheHill},
+    pickup::Pickup,
+    player::{DynPlayer, Player},
     pos::Pos,
+    result::Tiebreak,
+    zone::Bounds,
+};
+use protocol::{
+    class::ClassStats,
+    message::{ACCEPT_CONNECTION, SERVER_SHUTDOWN, UNKNOWN_CLASS},
+};
+use signal_hook::{consts::TERM_SIGNALS, flag};
+use tracing::{error, info, info_span, warn};
+use tungstenite::accept_hdr;
+use tungstenite::handshake::server::{
+    Callback, ErrorResponse, Request as HandshakeRequest, Response as HandshakeResponse,
 };
-use serde::Deserialize;
-use tungstenite::accept;
 use tungstenite::Message;
 
-#[derive(Deserialize)]
-struct Req {
-    pub actions: Vec<Action>,
+/// The server accepts clients and answers the admin API in their own threads, every game
+/// runs in its own thread too, the main thread matches the queued clients into games.
+pub struct Server<const PlayersOnGame: usize> {
+    shared: Arc<Shared>,
+    rules: Rules,
+}
+
+/// What the players have to do to win the games of the server.
+#[derive(Debug, Clone, Copy)]
+pub enum Objective {
+    /// The last team or player standing wins.
+    Elimination,
+    /// The flags of the teams stand in the corners of the map, it needs team games.
+    CaptureTheFlag { captures_to_win: u16 },
+    /// The hill is the center cell of the map.
+    KingOfTheHill { points_to_win: u16 },
+    /// Killed players come back in the corners of the map after `respawn_turns`.
+    Deathmatch {
+        kills_to_win: u16,
+        respawn_turns: u8,
+    },
 }
 
-pub struct Server<const PlayersOnGame: usize> {
-    players_without_games: VecDeque<Box<dyn DynClient>>,
-    games: VecDeque<(Vec<Box<dyn DynClient>>, Box<dyn LikeGame>, Duration)>,
+impl Objective {
+    fn mode(&self, map_size: u8, teams: u8) -> Box<dyn GameMode> {
+        let corners = corners(map_size);
+        match *self {
+            Objective::Elimination => Box::new(Elimination),
+            Objective::CaptureTheFlag { captures_to_win } => {
+                let bases: Vec<(u8, Pos)> = (0..teams.min(4))
+                    .map(|team| (team, corners[team as usize]))
+                    .collect();
+                Box::new(CaptureTheFlag::new(&bases, captures_to_win))
+            }
+            Objective::KingOfTheHill { points_to_win } => {
+                let center = center(map_size);
+                Box::new(KingOfTheHill::new(
+                    Bounds::new(center, center),
+                    points_to_win,
+                ))
+            }
+            Objective::Deathmatch {
+                kills_to_win,
+                respawn_turns,
+            } => Box::new(Deathmatch::new(
+                corners.to_vec(),
+                respawn_turns,
+                kills_to_win,
+            )),
+        }
+    }
+
+    /// Cells where the heroes have to stand: the flags, the hill and the respawns.
+    fn cells(&self, map_size: u8, teams: u8) -> Vec<Pos> {
+        match *self {
+            Objective::Elimination => Vec::new(),
+            Objective::CaptureTheFlag { .. } => corners(map_size)
+                .into_iter()
+                .take(teams.min(4) as usize)
+                .collect(),
+            Objective::KingOfTheHill { .. } => vec![center(map_size)],
+            Objective::Deathmatch { .. } => corners(map_size).to_vec(),
+        }
+    }
+}
+
+fn corners(map_size: u8) -> [Pos; 4] {
+    let max = map_size - 1;
+    [(0, max), (max, max), (0, 0), (max, 0)].map(|corner| corner.into())
+}
+
+fn center(map_size: u8) -> Pos {
+    let middle = (map_size - 1) / 2;
+    (middle, middle).into()
+}
+
+/// Players are split into teams by the order of coming, the bots complete the last teams.
+#[derive(Debug, Clone, Copy)]
+struct Teams {
+    size: usize,
+    friendly_fire: bool,
+}
+
+/// Settings of the server that every new game is built with.
+#[derive(Debug, Clone)]
+struct Rules {
+    bots_difficulty: Difficulty,
+    teams: Option<Teams>,
+    objective: Objective,
+    movement: Movement,
+    free_aim: bool,
+    map: Option<Map>,
+    pickups: Vec<Pickup>,
+    zone: Option<(u16, u16, u8)>,
+}
+
+/// Removes the game from the running ones when its thread ends, a panic included, so
+/// `shutdown` doesn't wait for it forever.
+struct GameGuard {
+    shared: Arc<Shared>,
+    id: u64,
+}
+
+impl Drop for GameGuard {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            error!(game = self.id, "game crashed");
+        }
+        self.shared
+            .games
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|game| game.info.id != self.id);
+        self.shared.games_changed.notify_all();
+    }
+}
+
+/// State of the server that all its threads use.
+struct Shared {
+    players_without_games: Mutex<VecDeque<(Box<dyn DynClient>, Duration)>>,
+    queue_changed: Condvar,
+    games: Mutex<Vec<GameHandle>>,
+    games_changed: Condvar,
+    results: Mutex<VecDeque<FinishedGame>>,
+    config: Mutex<Config>,
+    metrics: Metrics,
+    shutdown: Arc<AtomicBool>,
+    next_id: AtomicU64,
     instant: Instant,
+    /// Classes that the clients can pick in the handshake.
+    classes: Vec<ClassStats>,
+}
+
+/// Running game as the other threads see it, the game thread updates `info` every turn.
+struct GameHandle {
+    info: RunningGame,
+    commands: Sender<Command>,
 }
 
+const ADDRESS: &str = "127.0.0.1:8080";
+const ADMIN_ADDRESS: &str = "127.0.0.1:8081";
+
+/// A client that doesn't finish the handshake or doesn't read its messages for so long is dropped.
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often the main thread checks the shutdown flag.
+const SHUTDOWN_POLL: Duration = Duration::from_millis(100);
+
+/// Pickups of the games on the empty field, one of every kind.
+const PICKUPS: &str = include_str!("../../pickups.json");
+
+/// Character classes, a client picks one with the `class` parameter of the url, e.g. `/?class=tank`.
+const CLASSES: &str = include_str!("../../classes.json");
+
+#[derive(Debug)]
+pub enum ServerError {
+    Bind {
+        address: &'static str,
+        err: io::Error,
+    },
+    Signals(io::Error),
+    /// A hero, a pickup or an objective of the games would stand on the cell that
+    /// the heroes can't enter.
+    Map(Pos),
+}
+
+impl Display for ServerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerError::Bind { address, err } => write!(f, "Can't listen on {}: {}", address, err),
+            ServerError::Signals(err) => write!(f, "Can't handle the signals: {}", err),
+            ServerError::Map(pos) => write!(
+                f,
+                "The map has to be passable at ({}, {}), the games put a hero, a pickup or an objective there",
+                pos.x, pos.y
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ServerError {}
+
+/// How many finished games the admin API remembers.
+const RECENT_RESULTS: usize = 20;
+
 impl<const PlayersOnGame: usize> Server<PlayersOnGame> {
     pub fn new() -> Self {
+        let config = Config::new(
+            TickMode::Fixed(Duration::from_secs(2)),
+            Duration::from_secs(30),
+        );
         Self {
-            players_without_games: VecDeque::new(),
-            games: VecDeque::new(),
-            instant: Instant::now(),
+            shared: Arc::new(Shared {
+                players_without_games: Mutex::new(VecDeque::new()),
+                queue_changed: Condvar::new(),
+                games: Mutex::new(Vec::new()),
+                games_changed: Condvar::new(),
+                results: Mutex::new(VecDeque::new()),
+                config: Mutex::new(config),
+                metrics: Metrics::default(),
+                shutdown: Arc::new(AtomicBool::new(false)),
+                next_id: AtomicU64::new(0),
+                instant: Instant::now(),
+                classes: serde_json::from_str(CLASSES).unwrap(),
+            }),
+            rules: Rules {
+                bots_difficulty: Difficulty::Medium,
+                teams: None,
+                objective: Objective::Elimination,
+                movement: Movement::FourWay,
+                free_aim: false,
+                map: None,
+                pickups: serde_json::from_str(PICKUPS).unwrap(),
+                zone: None,
+            },
         }
     }
 
-    fn new_connections_handler(&mut self, connection: TcpStream) {
-        // Need refactor this shit!!!
-        sleep(Duration::from_millis(1));
-        match accept(connection) {
-            Ok(mut websocket) => {
-                if websocket.can_write() {
-                    if let Ok(..) =
-                        websocket.write_message(Message::Text(String::from("Accept connection")))
-                    {
-                        self.players_without_games
-                            .push_back(Box::new(Client::new(websocket)));
-                    }
+    pub fn with_objective(mut self, objective: Objective) -> Self {
+        self.rules.objective = objective;
+        self
+    }
+
+    /// `Movement::EightWay` lets the players move and shoot diagonally.
+    pub fn with_movement(mut self, movement: Movement) -> Self {
+        self.rules.movement = movement;
+        self
+    }
+
+    /// Free aim lets the players shoot at any cell by `Action::AttackAt`.
+    pub fn with_free_aim(mut self, free_aim: bool) -> Self {
+        self.rules.free_aim = free_aim;
+        self
+    }
+
+    /// The safe zone starts to shrink on `start_turn`, then shrinks every `shrink_every` turns
+    /// and deals `damage` to the heroes outside of it. Games have no zone without it.
+    pub fn with_zone(mut self, start_turn: u16, shrink_every: u16, damage: u8) -> Self {
+        self.rules.zone = Some((start_turn, shrink_every, damage));
+        self
+    }
+
+    /// Games are played on the map instead of the empty 5x5 field, the heroes start on the
+    /// bottom row from `x = 1`.
+    pub fn with_map(mut self, map: Map) -> Self {
+        self.rules.map = Some(map);
+        self
+    }
+
+    /// Spawn points of the pickups instead of the default ones from `pickups.json`.
+    pub fn with_pickups(mut self, pickups: Vec<Pickup>) -> Self {
+        self.rules.pickups = pickups;
+        self
+    }
+
+    /// Team games, e.g. `Server::<4>::new().with_teams(2, false)` plays 2v2.
+    pub fn with_teams(mut self, size: usize, friendly_fire: bool) -> Self {
+        self.rules.teams = Some(Teams {
+            size,
+            friendly_fire,
+        });
+        self
+    }
+
+    pub fn with_tick_mode(self, tick_mode: TickMode) -> Self {
+        let mut config = self.shared.config.lock().unwrap();
+        *config = Config::new(tick_mode, config.queue_timeout());
+        drop(config);
+        self
+    }
+
+    /// Clients that wait in the queue longer than `queue_timeout` play against bots.
+    pub fn with_bots(mut self, difficulty: Difficulty, queue_timeout: Duration) -> Self {
+        self.rules.bots_difficulty = difficulty;
+        let mut config = self.shared.config.lock().unwrap();
+        *config = Config::new(config.tick_mode(), queue_timeout);
+        drop(config);
+        self
+    }
+
+    /// Cells of the map that the games put the heroes, the pickups and the objective on,
+    /// the heroes start on the bottom row from `x = 1`.
+    fn start_cells(&self, map_size: u8) -> Vec<Pos> {
+        let teams_count = self
+            .rules
+            .teams
+            .map_or(0, |teams| PlayersOnGame.div_ceil(teams.size) as u8);
+        (1..=PlayersOnGame as u8)
+            .map(|x| Pos::new(x, 0))
+            .chain(self.rules.pickups.iter().map(|pickup| pickup.pos))
+            .chain(self.rules.objective.cells(map_size, teams_count))
+            .collect()
+    }
+
+    fn create_new_game(rules: Rules, clients: &mut Vec<Box<dyn DynClient>>) -> Box<dyn LikeGame> {
+        let Rules {
+            bots_difficulty,
+            teams,
+            objective,
+            movement,
+            free_aim,
+            map,
+            pickups,
+            zone,
+        } = rules;
+        let map_size = map.as_ref().map_or(5, |map| map.get_size());
+        let teams_count = teams.map_or(0, |teams| PlayersOnGame.div_ceil(teams.size) as u8);
+        let mut builder = GameBuilder::default();
+        let mut players = Vec::new();
+        let mut pos: Pos = (0, 0).into();
+        let mut id = 0;
+        for ind in 0..PlayersOnGame {
+            pos = pos.shift(Direction::Right).unwrap();
+            let team = |player: Player| match teams {
+                Some(teams) => player.with_team((ind / teams.size) as u8),
+                None => player,
+            };
+            if ind < clients.len() {
+                let player = match clients[ind].get_class() {
+                    Some(class) => Player::new_player(id, Character::from_class(class, pos)),
+                    None => Player::new_default_player(id, pos),
+                };
+                let pl: Box<dyn DynPlayer> = Box::new(team(player));
+                players.push(pl);
+                clients[ind].add_hero_id(id);
+            } else {
+                builder = builder.add_bot(
+                    Box::new(team(Player::new_default_bot(id, pos))),
+                    bots_difficulty.controller(),
+                );
+            }
+            id += 1;
+        }
+        if let Some((start_turn, shrink_every, damage)) = zone {
+            builder = builder.add_zone(start_turn, shrink_every, damage);
+        }
+        return builder
+            .add_actions_count(2)
+            .add_map(map.unwrap_or_else(|| Map::new_empty(map_size)))
+            .add_time_limit(1000)
+            .add_tiebreak(Tiebreak::Health)
+            .add_friendly_fire(teams.is_some_and(|teams| teams.friendly_fire))
+            .add_mode(objective.mode(map_size, teams_count))
+            .add_movement(movement)
+            .add_free_aim(free_aim)
+            .add_players(players)
+            .add_pickups(pickups)
+            .build();
+    }
+
+    fn game_selection(&self, queue: &mut VecDeque<(Box<dyn DynClient>, Duration)>) {
+        let queue_timeout = self.shared.config.lock().unwrap().queue_timeout();
+        let queue_timeout_over = match queue.front() {
+            Some((_, queued)) => *queued + queue_timeout < self.shared.instant.elapsed(),
+            None => false,
+        };
+        if queue.len() >= PlayersOnGame || queue_timeout_over {
+            let count = queue.len().min(PlayersOnGame);
+            let clients = queue.drain(..count).map(|(client, _)| client).collect();
+            self.start_game(clients);
+        }
+    }
+
+    fn start_game(&self, mut clients: Vec<Box<dyn DynClient>>) {
+        let id = self.shared.next_id();
+        info!(
+            game = id,
+            clients = clients.len(),
+            bots = PlayersOnGame - clients.len(),
+            "game started"
+        );
+        self.shared
+            .metrics
+            .games_started
+            .fetch_add(1, Ordering::Relaxed);
+        let (commands, receiver) = channel();
+        let mut games = self.shared.games.lock().unwrap();
+        let shared = Arc::clone(&self.shared);
+        let rules = self.rules.clone();
+        // The game is built in its thread, the players and the bots don't have to be `Send`
+        thread::spawn(move || {
+            let _guard = GameGuard {
+                shared: Arc::clone(&shared),
+                id,
+            };
+            let game = Self::create_new_game(rules, &mut clients);
+            clients.sort_by(|f, s| f.get_hero_id().unwrap().cmp(&s.get_hero_id().unwrap()));
+            shared.play(Session::new(id, clients, game), receiver);
+        });
+        games.push(GameHandle {
+            info: RunningGame {
+                id,
+                turn: 0,
+                players: Vec::new(),
+            },
+            commands,
+        });
+    }
+
+    /// In the step mode every line on the standard input moves all waiting games forward.
+    fn listen_operator(&self) {
+        let shared = Arc::clone(&self.shared);
+        thread::spawn(move || {
+            for _ in stdin().lines() {
+                for game in shared.games.lock().unwrap().iter() {
+                    let _ = game.commands.send(Command::Step);
                 }
             }
-            Err(err) => {
-                println!("{}", err);
+        });
+    }
+
+    /// The first Ctrl+C stops the server gracefully, the second one stops it at once.
+    fn listen_signals(&self) -> Result<(), ServerError> {
+        for signal in TERM_SIGNALS {
+            flag::register_conditional_shutdown(*signal, 1, Arc::clone(&self.shared.shutdown))
+                .map_err(ServerError::Signals)?;
+            flag::register(*signal, Arc::clone(&self.shared.shutdown))
+                .map_err(ServerError::Signals)?;
+        }
+        Ok(())
+    }
+
+    /// Queued clients leave at once, running games are played to the end.
+    fn shutdown(&self) {
+        info!(
+            games = self.shared.games.lock().unwrap().len(),
+            "shutting down, press Ctrl+C again to stop at once"
+        );
+        // The acceptor is blocked on `accept`, this connection wakes it up to see the flag
+        let _ = TcpStream::connect(ADDRESS);
+        let queued: Vec<_> = self
+            .shared
+            .players_without_games
+            .lock()
+            .unwrap()
+            .drain(..)
+            .collect();
+        for (mut client, _) in queued {
+            let websocket = client.get_websocket();
+            let _ = websocket.write_message(Message::Text(String::from(SERVER_SHUTDOWN)));
+            let _ = websocket.close(None);
+            let _ = websocket.write_pending();
+        }
+        let mut games = self.shared.games.lock().unwrap();
+        while !games.is_empty() {
+            games = self.shared.games_changed.wait(games).unwrap();
+        }
+        info!("server stopped");
+    }
+
+    /// Serves the clients until a termination signal, then waits for the running games.
+    pub fn run(&mut self) -> Result<(), ServerError> {
+        if let Some(map) = &self.rules.map {
+            let blocked = self
+                .start_cells(map.get_size())
+                .into_iter()
+                .find(|pos| !map.pos_in_map(*pos) || !map.get_cell(*pos).can_move());
+            if let Some(pos) = blocked {
+                return Err(ServerError::Map(pos));
+            }
+        }
+        self.listen_signals()?;
+        let listener = TcpListener::bind(ADDRESS).map_err(|err| ServerError::Bind {
+            address: ADDRESS,
+            err,
+        })?;
+        let admin = Admin::bind(ADMIN_ADDRESS).map_err(|err| ServerError::Bind {
+            address: ADMIN_ADDRESS,
+            err,
+        })?;
+        info!(address = ADDRESS, admin = ADMIN_ADDRESS, "server started");
+        let shared = Arc::clone(&self.shared);
+        thread::spawn(move || shared.accept_clients(listener));
+        let shared = Arc::clone(&self.shared);
+        thread::spawn(move || loop {
+            if let Some((mut stream, request)) = admin.accept() {
+                let _ = shared.admin_handler(&request).send(&mut stream);
             }
+        });
+        if let TickMode::Step = self.shared.config.lock().unwrap().tick_mode() {
+            self.listen_operator();
+        }
+        let mut queue = self.shared.players_without_games.lock().unwrap();
+        while !self.shared.shutdown.load(Ordering::Relaxed) {
+            self.game_selection(&mut queue);
+            // Sleeps until a client comes, the first one in the queue waited enough or the check of the flag
+            let queue_timeout = self.shared.config.lock().unwrap().queue_timeout();
+            let wait = match queue.front() {
+                Some((_, queued)) => (*queued + queue_timeout)
+                    .saturating_sub(self.shared.instant.elapsed())
+                    .min(SHUTDOWN_POLL),
+                None => SHUTDOWN_POLL,
+            };
+            queue = self
+                .shared
+                .queue_changed
+                .wait_timeout(queue, wait)
+                .unwrap()
+                .0;
         }
+        drop(queue);
+        self.shutdown();
+        Ok(())
+    }
+}
+
+impl Shared {
+    fn next_id(&self) -> u64 {
+        self.next_id.fetch_add(1, Ordering::Relaxed) + 1
     }
 
-    fn get_action_from_client(
-        &mut self,
-        client: &mut dyn DynClient,
-        game: &Box<dyn LikeGame>,
-    ) -> Vec<Action> {
-        if let Ok(Message::Text(text)) = client.get_websocket().read_message() {
-            let req: Result<Req, serde_json::Error> = serde_json::from_str(text.as_str());
-            if let Ok(actions) = req {
-                let mut actions = actions.actions;
-                game.validate_actions(&mut actions);
-                return actions;
+    /// Only accepts the connections, every handshake runs in its own thread so a silent client
+    /// doesn't hold the others.
+    fn accept_clients(self: Arc<Self>, listener: TcpListener) {
+        for connection in listener.incoming() {
+            if self.shutdown.load(Ordering::Relaxed) {
+                return;
+            }
+            match connection {
+                Ok(connection) => {
+                    let shared = Arc::clone(&self);
+                    thread::spawn(move || shared.new_connections_handler(connection));
+                }
+                Err(err) => warn!(%err, "connection not accepted"),
             }
         }
-        return vec![Action::Nothing; game.get_actions_count()];
     }
 
-    fn games_update(&mut self) {
-        if let Some((mut clients, mut game, timeout)) = self.games.pop_front() {
-            if timeout < self.instant.elapsed() {
-                if let GameState::TimeIsOver { winners } | GameState::End { winners } = game.state()
-                {
-                    for client in clients.iter_mut() {
-                        if winners.contains(&client.get_hero_id().unwrap()) {
-                            let _ = client
-                                .get_websocket()
-                                .write_message(Message::Text(String::from("Win")));
-                        } else {
-                            let _ = client
-                                .get_websocket()
-                                .write_message(Message::Text(String::from("Lose")));
+    fn new_connections_handler(&self, connection: TcpStream) {
+        let address = connection.peer_addr().ok();
+        let _ = connection.set_read_timeout(Some(SOCKET_TIMEOUT));
+        let _ = connection.set_write_timeout(Some(SOCKET_TIMEOUT));
+        let mut query = None;
+        let handshake = accept_hdr(connection, QueryCallback(&mut query));
+        match handshake {
+            Ok(mut websocket) => {
+                let id = self.next_id();
+                let _entered = info_span!("client", id).entered();
+                let class = match requested_class(query.as_deref()) {
+                    Some(name) => match self.classes.iter().find(|class| class.name == name) {
+                        Some(class) => Some(class.clone()),
+                        None => {
+                            warn!(?address, class = name, "unknown class");
+                            self.metrics
+                                .handshake_errors
+                                .fetch_add(1, Ordering::Relaxed);
+                            let _ =
+                                websocket.write_message(Message::Text(UNKNOWN_CLASS.to_string()));
+                            let _ = websocket.close(None);
+                            let _ = websocket.write_pending();
+                            return;
                         }
-                    }
-                    return;
-                }
-                if let GameState::Continue { info } | GameState::NotStarted { info } = game.state()
-                {
-                    for client in clients.iter_mut() {
-                        if let Ok(..) = client
-                            .get_websocket()
-                            .write_message(Message::Text(serde_json::to_string(info).unwrap()))
-                        {
+                    },
+                    None => None,
+                };
+                if websocket.can_write() {
+                    match websocket.write_message(Message::Text(String::from(ACCEPT_CONNECTION))) {
+                        Ok(..) => {
+                            info!(
+                                ?address,
+                                class = class.as_ref().map(|class| class.name.as_str()),
+                                "client connected"
+                            );
+                            self.metrics.connections.fetch_add(1, Ordering::Relaxed);
+                            let client = Client::new(id, websocket, class);
+                            self.players_without_games
+                                .lock()
+                                .unwrap()
+                                .push_back((Box::new(client), self.instant.elapsed()));
+                            self.queue_changed.notify_one();
+                        }
+                        Err(err) => {
+                            warn!(%err, "accept message not sent");
+                            self.metrics.send_errors.fetch_add(1, Ordering::Relaxed);
                         }
                     }
                 }
-                let mut actions = Vec::new();
-                clients.iter_mut().for_each(|client| {
-                    actions.push(self.get_action_from_client(client.as_mut(), &game))
-                });
-                game.update(actions);
-                self.games.push_back((
-                    clients,
-                    game,
-                    self.instant.elapsed() + Duration::from_secs(2),
-                ));
-            } else {
-                self.games.push_back((clients, game, timeout));
+            }
+            Err(err) => {
+                warn!(?address, %err, "websocket handshake failed");
+                self.metrics
+                    .handshake_errors
+                    .fetch_add(1, Ordering::Relaxed);
             }
         }
     }
 
-    fn create_new_game(&self, clients: &mut Vec<Box<dyn DynClient>>) -> Box<dyn LikeGame> {
-        let mut players = Vec::new();
-        let mut pos: Pos = (0, 0).into();
-        let mut id = 0;
-        for ind in 0..PlayersOnGame {
-            pos = pos.shift(Direction::Right).unwrap();
-            let pl: Box<dyn DynPlayer> = Box::new(Player::new_default_player(id, pos));
-            players.push(pl);
-            clients[ind].add_hero_id(id);
-            id += 1;
+    /// Body of the game thread.
+    fn play(&self, mut session: Session, commands: Receiver<Command>) {
+        let id = session.get_id();
+        loop {
+            let mut aborted = false;
+            for command in commands.try_iter() {
+                match command {
+                    Command::Step => session.request_step(),
+                    Command::Kick(client_id) => {
+                        session.kick(client_id);
+                    }
+                    Command::Abort => aborted = true,
+                }
+            }
+            if aborted {
+                session.abort();
+                self.metrics.games_aborted.fetch_add(1, Ordering::Relaxed);
+                break;
+            }
+            let tick_mode = self.config.lock().unwrap().tick_mode();
+            let continues = session.update(tick_mode, &self.metrics);
+            let info = session.info();
+            if let Some(game) = self
+                .games
+                .lock()
+                .unwrap()
+                .iter_mut()
+                .find(|game| game.info.id == id)
+            {
+                game.info = info;
+            }
+            if !continues {
+                if let Some(result) = session.result() {
+                    self.metrics.games_finished.fetch_add(1, Ordering::Relaxed);
+                    let mut results = self.results.lock().unwrap();
+                    if results.len() == RECENT_RESULTS {
+                        results.pop_front();
+                    }
+                    results.push_back(FinishedGame {
+                        id,
+                        result: result.clone(),
+                    });
+                }
+                if self.shutdown.load(Ordering::Relaxed) {
+                    session.close(Some(SERVER_SHUTDOWN));
+                }
+                break;
+            }
         }
-        return GameBuilder::default()
-            .add_actions_count(2)
-            .add_map_size(5)
-            .add_time_limit(1000)
-            .add_players(players)
-            .build();
     }
 
-    fn game_selection(&mut self) {
-        if self.players_without_games.len() >= PlayersOnGame {
-            let mut clients = Vec::with_capacity(PlayersOnGame);
-            for _ in 0..PlayersOnGame {
-                clients.push(self.players_without_games.pop_front().unwrap());
-            }
-            let game = self.create_new_game(&mut clients);
-            clients.sort_by(|f, s| f.get_hero_id().unwrap().cmp(&s.get_hero_id().unwrap()));
-            self.games.push_back((
-                clients,
-                game,
-                self.instant.elapsed() + Duration::from_secs(2),
-            ));
+    /// Sends the command to the game, `false` if there is no such game.
+    fn command(&self, game_id: Option<u64>, command: Command) -> bool {
+        let games = self.games.lock().unwrap();
+        match games.iter().find(|game| Some(game.info.id) == game_id) {
+            Some(game) => game.commands.send(command).is_ok(),
+            None => false,
         }
     }
 
-    pub fn run(&mut self) {
-        let server = TcpListener::bind("127.0.0.1:8080").unwrap();
-        server.set_nonblocking(true).unwrap();
-        for stream in server.incoming() {
-            match stream {
-                Ok(s) => {
-                    self.new_connections_handler(s);
+    fn admin_handler(&self, request: &Request) -> Response {
+        let path: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+        match (request.method.as_str(), path.as_slice()) {
+            ("GET", ["clients"]) => {
+                let now = self.instant.elapsed();
+                let clients: Vec<QueuedClient> = self
+                    .players_without_games
+                    .lock()
+                    .unwrap()
+                    .iter_mut()
+                    .map(|(client, queued)| QueuedClient {
+                        id: client.get_id(),
+                        address: client
+                            .get_websocket()
+                            .get_ref()
+                            .peer_addr()
+                            .ok()
+                            .map(|addr| addr.to_string()),
+                        class: client.get_class().map(|class| class.name.clone()),
+                        waiting_ms: (now - *queued).as_millis() as u64,
+                    })
+                    .collect();
+                Response::json(&clients)
+            }
+            ("GET", ["games"]) => {
+                let games: Vec<RunningGame> = self
+                    .games
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .map(|game| game.info.clone())
+                    .collect();
+                Response::json(&games)
+            }
+            ("GET", ["results"]) => Response::json(&*self.results.lock().unwrap()),
+            ("GET", ["metrics"]) => {
+                // One lock at a time, the matchmaking loop takes them in the other order
+                let games = self.games.lock().unwrap().len();
+                let queued = self.players_without_games.lock().unwrap().len();
+                Response::text(self.metrics.render(games, queued))
+            }
+            ("GET", ["config"]) => Response::json(&*self.config.lock().unwrap()),
+            ("POST", ["config"]) => match serde_json::from_str::<Config>(&request.body) {
+                Ok(config) => {
+                    *self.config.lock().unwrap() = config;
+                    self.queue_changed.notify_one();
+                    Response::json(&config)
                 }
-                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                    self.games_update();
-                    self.game_selection();
+                Err(err) => Response::error(400, &err.to_string()),
+            },
+            ("POST", ["clients", id, "kick"]) => {
+                let id = id.parse().ok();
+                let mut queue = self.players_without_games.lock().unwrap();
+                let queued = queue
+                    .iter()
+                    .position(|(client, _)| Some(client.get_id()) == id);
+                if let Some(index) = queued {
+                    let (mut client, _) = queue.remove(index).unwrap();
+                    let _ = client.get_websocket().close(None);
+                    let _ = client.get_websocket().write_pending();
+                    return Response::json(&"kicked");
                 }
-                Err(ref e) => {
-                    panic!("{:?}", e);
+                drop(queue);
+                let game_id = self
+                    .games
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|game| {
+                        game.info
+                            .players
+                            .iter()
+                            .any(|player| Some(player.client_id) == id)
+                    })
+                    .map(|game| game.info.id);
+                match id {
+                    Some(id) if self.command(game_id, Command::Kick(id)) => {
+                        Response::json(&"kicked")
+                    }
+                    _ => Response::error(404, "no such client"),
                 }
             }
+            ("POST", ["games", id, "abort"]) => {
+                if self.command(id.parse().ok(), Command::Abort) {
+                    Response::json(&"aborted")
+                } else {
+                    Response::error(404, "no such game")
+                }
+            }
+            ("POST", ["games", id, "step"]) => {
+                if self.command(id.parse().ok(), Command::Step) {
+                    Response::json(&"step")
+                } else {
+                    Response::error(404, "no such game")
+                }
+            }
+            _ => Response::error(404, "unknown request"),
         }
     }
 }
+
+/// Keeps the query of the handshake url.
+struct QueryCallback<'a>(&'a mut Option<String>);
+
+impl Callback for QueryCallback<'_> {
+    fn on_request(
+        self,
+        request: &HandshakeRequest,
+        response: HandshakeResponse,
+    ) -> Result<HandshakeResponse, ErrorResponse> {
+        *self.0 = request.uri().query().map(String::from);
+        Ok(response)
+    }
+}
+
+/// Value of the `class` parameter in the query of the handshake url.
+fn requested_class(query: Option<&str>) -> Option<&str> {
+    query?
+        .split('&')
+        .find_map(|pair| match pair.split_once('=') {
+            Some(("class", name)) => Some(name),
+            _ => None,
+        })
+}
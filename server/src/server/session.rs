@@ -0,0 +1,317 @@
+use std::{
+    io::ErrorKind,
+    sync::atomic::Ordering,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use super::{
+    admin::{GamePlayer, RunningGame},
+    client::DynClient,
+    metrics::Metrics,
+};
+use core::{
+    game::{GameState, LikeGame},
+    player::Action,
+    result::GameResult,
+};
+use protocol::message::{MatchEnd, Outcome, Response};
+use tracing::{info, info_span, warn, Span};
+use tungstenite::{Error, Message};
+
+/// The longest time `Session::update` blocks, so the game thread notices commands and new settings.
+const POLL: Duration = Duration::from_millis(50);
+
+/// When the server moves a game to the next turn.
+#[derive(Debug, Clone, Copy)]
+pub enum TickMode {
+    /// Every tick, clients that didn't answer in time do nothing.
+    Fixed(Duration),
+    /// As soon as every client answered, but no later than the timeout.
+    Fast(Duration),
+    /// When every client answered or the operator asked for a step, without a timeout.
+    Step,
+}
+
+/// Requests of the operator to the thread of the game.
+pub enum Command {
+    Step,
+    Kick(u64),
+    Abort,
+}
+
+/// Game with the clients that play it.
+pub struct Session {
+    id: u64,
+    // The hero of a kicked or disconnected client stays in the game and does nothing
+    clients: Vec<Option<Box<dyn DynClient>>>,
+    game: Box<dyn LikeGame>,
+    actions: Vec<Option<Vec<Action>>>,
+    waiting_since: Option<Instant>,
+    step_requested: bool,
+    span: Span,
+}
+
+impl Session {
+    pub fn new(id: u64, clients: Vec<Box<dyn DynClient>>, game: Box<dyn LikeGame>) -> Self {
+        Self {
+            id,
+            actions: vec![None; clients.len()],
+            clients: clients.into_iter().map(Some).collect(),
+            game,
+            waiting_since: None,
+            step_requested: false,
+            span: info_span!("game", id),
+        }
+    }
+
+    pub fn get_id(&self) -> u64 {
+        self.id
+    }
+
+    /// What the admin API shows about the game.
+    pub fn info(&self) -> RunningGame {
+        RunningGame {
+            id: self.id,
+            turn: self.game.get_turn(),
+            players: self
+                .clients
+                .iter()
+                .flatten()
+                .map(|client| GamePlayer {
+                    client_id: client.get_id(),
+                    hero_id: client.get_hero_id().unwrap(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Result of the match, when it's over.
+    pub fn result(&self) -> Option<&GameResult> {
+        match self.game.state() {
+            GameState::TimeIsOver { result } | GameState::End { result } => Some(result),
+            _ => None,
+        }
+    }
+
+    /// Operator step for the `TickMode::Step`, the game advances without waiting for the clients.
+    pub fn request_step(&mut self) {
+        self.step_requested = true;
+    }
+
+    /// Closes the connection of the client, returns `false` if the client doesn't play here.
+    pub fn kick(&mut self, client_id: u64) -> bool {
+        for slot in self.clients.iter_mut() {
+            if let Some(client) = slot.as_mut().filter(|client| client.get_id() == client_id) {
+                self.span
+                    .in_scope(|| info!(client = client_id, "client kicked"));
+                let _ = client.get_websocket().close(None);
+                let _ = client.get_websocket().write_pending();
+                *slot = None;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Stops the match without a result and closes all connections.
+    pub fn abort(&mut self) {
+        self.span.in_scope(|| info!("game aborted"));
+        self.close(None);
+    }
+
+    /// Closes all connections, the clients get the `farewell` message before it.
+    pub fn close(&mut self, farewell: Option<&str>) {
+        for client in self.clients.iter_mut().flatten() {
+            if let Some(text) = farewell {
+                let _ = client
+                    .get_websocket()
+                    .write_message(Message::Text(text.to_string()));
+            }
+            let _ = client.get_websocket().close(None);
+            let _ = client.get_websocket().write_pending();
+        }
+        self.clients.clear();
+    }
+
+    /// Sends the state or waits for the actions and moves the game forward, blocks no longer
+    /// than `POLL`, returns `false` when the match is over or nobody plays it anymore.
+    pub fn update(&mut self, mode: TickMode, metrics: &Metrics) -> bool {
+        let span = self.span.clone();
+        let _entered = span.enter();
+        if self.clients.iter().all(Option::is_none) && self.result().is_none() {
+            info!("all clients left, game stopped");
+            metrics.games_aborted.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        let waiting_since = match self.waiting_since {
+            Some(waiting_since) => waiting_since,
+            None => {
+                if let Some(result) = self.result() {
+                    info!(winners = ?result.winners, turn = self.game.get_turn(), "game finished");
+                    self.send_result(metrics);
+                    return false;
+                }
+                self.send_state(metrics);
+                self.waiting_since = Some(Instant::now());
+                return true;
+            }
+        };
+        let deadline = match mode {
+            TickMode::Fixed(timeout) | TickMode::Fast(timeout) => Some(waiting_since + timeout),
+            TickMode::Step => None,
+        };
+        let wait = deadline.map_or(POLL, |deadline| {
+            deadline.saturating_duration_since(Instant::now()).min(POLL)
+        });
+        self.read_actions(wait, metrics);
+        let answered = self.actions.iter().all(Option::is_some);
+        let timed_out = deadline.is_some_and(|deadline| deadline <= Instant::now());
+        let ready = match mode {
+            TickMode::Fixed(_) => timed_out,
+            TickMode::Fast(_) => answered || timed_out,
+            TickMode::Step => answered || self.step_requested,
+        };
+        if ready {
+            metrics.observe_turn_latency(waiting_since.elapsed());
+            self.next_turn(metrics);
+        } else if answered {
+            // Nothing to read until the end of the fixed tick
+            sleep(wait);
+        }
+        true
+    }
+
+    fn next_turn(&mut self, metrics: &Metrics) {
+        let actions_count = self.game.get_actions_count();
+        let actions = self
+            .actions
+            .iter_mut()
+            .zip(self.clients.iter())
+            .map(|(actions, client)| {
+                actions.take().unwrap_or_else(|| {
+                    if let Some(client) = client {
+                        metrics.timeouts.fetch_add(1, Ordering::Relaxed);
+                        client.get_span().in_scope(|| info!("no answer in time"));
+                    }
+                    vec![Action::Nothing; actions_count]
+                })
+            })
+            .collect();
+        metrics.turns.fetch_add(1, Ordering::Relaxed);
+        self.game.update(actions);
+        self.waiting_since = None;
+        self.step_requested = false;
+    }
+
+    /// Every client gets the game as its hero sees it.
+    fn send_state(&mut self, metrics: &Metrics) {
+        for client in self.clients.iter_mut().flatten() {
+            if let Some(info) = self.game.view(client.get_hero_id().unwrap()) {
+                skip_late_answers(client.as_mut());
+                send(
+                    client.as_mut(),
+                    serde_json::to_string(&info).unwrap(),
+                    metrics,
+                );
+            }
+        }
+    }
+
+    fn send_result(&mut self, metrics: &Metrics) {
+        if let GameState::TimeIsOver { result } | GameState::End { result } = self.game.state() {
+            for client in self.clients.iter_mut().flatten() {
+                let id = client.get_hero_id().unwrap();
+                let outcome = if result.winners.contains(&id) {
+                    Outcome::Win
+                } else if result.draw.contains(&id) {
+                    Outcome::Draw
+                } else {
+                    Outcome::Lose
+                };
+                let text = serde_json::to_string(&MatchEnd {
+                    outcome,
+                    result: result.clone(),
+                })
+                .unwrap();
+                send(client.as_mut(), text, metrics);
+            }
+        }
+    }
+
+    /// Reads the answers that came during `wait`.
+    fn read_actions(&mut self, wait: Duration, metrics: &Metrics) {
+        let until = Instant::now() + wait;
+        let nothing = vec![Action::Nothing; self.game.get_actions_count()];
+        for (slot, actions) in self.clients.iter_mut().zip(self.actions.iter_mut()) {
+            if actions.is_some() {
+                continue;
+            }
+            let client = match slot {
+                Some(client) => client,
+                None => {
+                    *actions = Some(nothing.clone());
+                    continue;
+                }
+            };
+            // A zero timeout means blocking forever, so the clients after the deadline get a moment
+            let timeout = until
+                .saturating_duration_since(Instant::now())
+                .max(Duration::from_millis(1));
+            let _ = client
+                .get_websocket()
+                .get_mut()
+                .set_read_timeout(Some(timeout));
+            match client.get_websocket().read_message() {
+                Ok(Message::Text(text)) => {
+                    *actions = match serde_json::from_str::<Response>(text.as_str()) {
+                        Ok(response) => {
+                            let mut client_actions = response.actions;
+                            self.game.validate_actions(&mut client_actions);
+                            Some(client_actions)
+                        }
+                        Err(err) => {
+                            metrics.malformed_messages.fetch_add(1, Ordering::Relaxed);
+                            client
+                                .get_span()
+                                .in_scope(|| warn!(%err, "malformed actions"));
+                            Some(nothing.clone())
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(Error::Io(err)) if is_timeout(&err) => {}
+                Err(err) => {
+                    metrics.disconnects.fetch_add(1, Ordering::Relaxed);
+                    client
+                        .get_span()
+                        .in_scope(|| warn!(%err, "connection lost"));
+                    *slot = None;
+                    *actions = Some(nothing.clone());
+                }
+            }
+        }
+    }
+}
+
+fn is_timeout(err: &std::io::Error) -> bool {
+    matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut)
+}
+
+/// Answers that came too late for the previous turn must not go to the next one.
+fn skip_late_answers(client: &mut dyn DynClient) {
+    let websocket = client.get_websocket();
+    if websocket.get_mut().set_nonblocking(true).is_ok() {
+        while websocket.read_message().is_ok() {}
+        let _ = websocket.get_mut().set_nonblocking(false);
+    }
+}
+
+fn send(client: &mut dyn DynClient, text: String, metrics: &Metrics) {
+    if let Err(err) = client.get_websocket().write_message(Message::Text(text)) {
+        metrics.send_errors.fetch_add(1, Ordering::Relaxed);
+        client
+            .get_span()
+            .in_scope(|| warn!(%err, "message not sent"));
+    }
+}